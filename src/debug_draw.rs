@@ -0,0 +1,414 @@
+use std::ops::RangeInclusive;
+
+use bevy::{
+  color::{
+    Color,
+    palettes::css::{LIME, RED},
+  },
+  ecs::system::Resource,
+  gizmos::gizmos::Gizmos,
+  math::{Rect, Vec2, Vec2Swizzles},
+};
+
+use crate::{
+  win_info::WinInfo,
+  world_unit::{AspectRatio, WorldAabb, WorldRect, WorldUnit, WorldVec2},
+};
+
+/// When inserted and enabled, `WorldInitPlugin` draws grid lines every `spacing` world units across
+/// the visible world, with the axes highlighted.
+#[derive(Resource)]
+pub struct WorldGrid {
+  pub spacing: WorldUnit,
+  pub enabled: bool,
+}
+
+impl Default for WorldGrid {
+  fn default() -> Self {
+    Self { spacing: WorldUnit::ONE, enabled: true }
+  }
+}
+
+/// Returns the center and size of `rect`, centered at `center`, in absolute pixel coordinates.
+fn pixel_bounds(
+  center: WorldVec2,
+  rect: &WorldRect,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) -> (Vec2, Vec2) {
+  let half_size = rect.half_size();
+  let size = Vec2 {
+    x: 2. * half_size.x.to_x(win_info, aspect_ratio),
+    y: 2. * half_size.y.to_y(win_info, aspect_ratio),
+  };
+  (center.to_absolute(win_info, aspect_ratio), size)
+}
+
+/// Draws a line between two world-space points.
+pub fn draw_world_line(
+  gizmos: &mut Gizmos,
+  start: WorldVec2,
+  end: WorldVec2,
+  color: impl Into<Color>,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) {
+  gizmos.line_2d(
+    start.to_absolute(win_info, aspect_ratio),
+    end.to_absolute(win_info, aspect_ratio),
+    color,
+  );
+}
+
+/// Draws the outline of `rect`, centered at `center`.
+pub fn draw_world_rect(
+  gizmos: &mut Gizmos,
+  center: WorldVec2,
+  rect: &WorldRect,
+  color: impl Into<Color>,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) {
+  let (center, size) = pixel_bounds(center, rect, win_info, aspect_ratio);
+  gizmos.rect_2d(center, size, color);
+}
+
+/// The most lines `draw_world_rect_filled` draws for a single rect.
+const MAX_FILL_LINES: u32 = 4096;
+
+/// Returns the lines, each `line_width` pixels wide, that fill `rect`. The lines run along the
+/// longer side of the rect and are inset by half a line width, so they don't bleed past its edges;
+/// only the last line may overlap its neighbor. Line widths below a pixel are treated as one pixel.
+/// Returns no lines if filling the rect would take more than `MAX_FILL_LINES`.
+fn fill_lines(rect: Rect, line_width: f32) -> Vec<(Vec2, Vec2)> {
+  if rect.is_empty() {
+    return Vec::new();
+  }
+
+  let line_width = line_width.max(1.);
+  let (rect, transpose) = if rect.width() <= rect.height() {
+    (rect, false)
+  } else {
+    (Rect::from_corners(rect.min.yx(), rect.max.yx()), true)
+  };
+  let count = (rect.width() / line_width).ceil();
+  if count.is_nan() || count > MAX_FILL_LINES as f32 {
+    return Vec::new();
+  }
+  let offsets: Vec<f32> = if rect.width() <= line_width {
+    vec![rect.center().x]
+  } else {
+    (0..count as u32)
+      .map(|i| (rect.min.x + line_width * (i as f32 + 0.5)).min(rect.max.x - line_width / 2.))
+      .collect()
+  };
+
+  offsets
+    .into_iter()
+    .map(|x| {
+      let (start, end) = (Vec2::new(x, rect.min.y), Vec2::new(x, rect.max.y));
+      if transpose {
+        (start.yx(), end.yx())
+      } else {
+        (start, end)
+      }
+    })
+    .collect()
+}
+
+/// Fills `rect`, in absolute pixel coordinates, with gizmo lines.
+fn fill_pixel_rect(gizmos: &mut Gizmos, rect: Rect, color: Color) {
+  for (start, end) in fill_lines(rect, gizmos.config.line_width) {
+    gizmos.line_2d(start, end, color);
+  }
+}
+
+/// Fills `rect`, centered at `center`, with gizmo lines tiled at the gizmo line width. Use a color
+/// with alpha < 1 to highlight a region without hiding what's underneath. Draws nothing if the rect
+/// is too large to fill with `MAX_FILL_LINES` lines.
+pub fn draw_world_rect_filled(
+  gizmos: &mut Gizmos,
+  center: WorldVec2,
+  rect: &WorldRect,
+  color: impl Into<Color>,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) {
+  let (center, size) = pixel_bounds(center, rect, win_info, aspect_ratio);
+  fill_pixel_rect(gizmos, Rect::from_center_size(center, size), color.into());
+}
+
+/// Returns the two regions of the window, in pixels from the window's center, left uncovered by the
+/// visible world: left and right bars if the window is wider than the world, or bottom and top bars
+/// if it is taller. The bars have zero size if the window matches the world's aspect ratio.
+pub fn letterbox_bars(win_info: &WinInfo, aspect_ratio: &AspectRatio) -> [Rect; 2] {
+  let world_size = Vec2 {
+    x: WorldUnit::screen_width(aspect_ratio).to_x(win_info, aspect_ratio),
+    y: WorldUnit::screen_height(aspect_ratio).to_y(win_info, aspect_ratio),
+  };
+  let window_half_size = Vec2::new(win_info.width, win_info.height) / 2.;
+  let world_half_size = world_size / 2.;
+
+  if world_size.x < win_info.width {
+    [
+      Rect::from_corners(
+        -window_half_size,
+        Vec2::new(-world_half_size.x, window_half_size.y),
+      ),
+      Rect::from_corners(
+        Vec2::new(world_half_size.x, -window_half_size.y),
+        window_half_size,
+      ),
+    ]
+  } else {
+    [
+      Rect::from_corners(
+        -window_half_size,
+        Vec2::new(window_half_size.x, -world_half_size.y),
+      ),
+      Rect::from_corners(
+        Vec2::new(-window_half_size.x, world_half_size.y),
+        window_half_size,
+      ),
+    ]
+  }
+}
+
+/// Covers the parts of the window outside the visible world with `color`.
+pub fn draw_letterbox_bars(
+  gizmos: &mut Gizmos,
+  color: impl Into<Color>,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) {
+  draw_letterbox_bars_at(gizmos, color, WorldVec2::ZERO, win_info, aspect_ratio);
+}
+
+/// Like `draw_letterbox_bars`, for a camera centered on `camera_pos`.
+pub(crate) fn draw_letterbox_bars_at(
+  gizmos: &mut Gizmos,
+  color: impl Into<Color>,
+  camera_pos: WorldVec2,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) {
+  let color = color.into();
+  let camera_offset = camera_pos.to_absolute(win_info, aspect_ratio);
+  for bar in letterbox_bars(win_info, aspect_ratio) {
+    fill_pixel_rect(
+      gizmos,
+      Rect::from_corners(bar.min + camera_offset, bar.max + camera_offset),
+      color,
+    );
+  }
+}
+
+/// Draws the edges of the visible world and a crosshair at the world origin.
+pub fn draw_world_overlay(gizmos: &mut Gizmos, win_info: &WinInfo, aspect_ratio: &AspectRatio) {
+  let screen = WorldAabb::screen(aspect_ratio);
+  let half_size = screen.half_size();
+  let screen_rect = WorldRect::new(half_size.x * 2., half_size.y * 2.);
+  draw_world_rect(
+    gizmos,
+    screen.center(),
+    &screen_rect,
+    LIME,
+    win_info,
+    aspect_ratio,
+  );
+
+  draw_world_line(
+    gizmos,
+    -WorldVec2::X,
+    WorldVec2::X,
+    RED,
+    win_info,
+    aspect_ratio,
+  );
+  draw_world_line(
+    gizmos,
+    -WorldVec2::Y,
+    WorldVec2::Y,
+    RED,
+    win_info,
+    aspect_ratio,
+  );
+}
+
+/// The most lines `draw_world_grid` draws along either axis.
+const MAX_GRID_LINES: i32 = 4096;
+
+/// Returns the indices `i` of the grid lines at `i * spacing` that fall within `min..=max`, or
+/// `None` if there would be more than `MAX_GRID_LINES` of them.
+fn grid_line_indices(
+  min: WorldUnit,
+  max: WorldUnit,
+  spacing: WorldUnit,
+) -> Option<RangeInclusive<i32>> {
+  let spacing = spacing.to_untyped();
+  let first = (min.to_untyped() / spacing).ceil() as i32;
+  let last = (max.to_untyped() / spacing).floor() as i32;
+  if last.saturating_sub(first) >= MAX_GRID_LINES {
+    return None;
+  }
+  Some(first..=last)
+}
+
+/// Draws grid lines every `spacing` world units across `bounds`, with the lines through the origin
+/// drawn in `axis_color`. Draws nothing if `spacing` is not finite and positive, or if it is less
+/// than a pixel on screen.
+pub fn draw_world_grid(
+  gizmos: &mut Gizmos,
+  spacing: WorldUnit,
+  bounds: &WorldAabb,
+  color: impl Into<Color>,
+  axis_color: impl Into<Color>,
+  win_info: &WinInfo,
+  aspect_ratio: &AspectRatio,
+) {
+  if !spacing.to_untyped().is_finite()
+    || spacing <= WorldUnit::ZERO
+    || spacing.to_x(win_info, aspect_ratio) < 1.
+    || spacing.to_y(win_info, aspect_ratio) < 1.
+  {
+    return;
+  }
+  let color = color.into();
+  let axis_color = axis_color.into();
+
+  for i in grid_line_indices(bounds.min.x, bounds.max.x, spacing)
+    .into_iter()
+    .flatten()
+  {
+    let x = spacing * i as f32;
+    draw_world_line(
+      gizmos,
+      WorldVec2::new(x, bounds.min.y),
+      WorldVec2::new(x, bounds.max.y),
+      if i == 0 { axis_color } else { color },
+      win_info,
+      aspect_ratio,
+    );
+  }
+  for i in grid_line_indices(bounds.min.y, bounds.max.y, spacing)
+    .into_iter()
+    .flatten()
+  {
+    let y = spacing * i as f32;
+    draw_world_line(
+      gizmos,
+      WorldVec2::new(bounds.min.x, y),
+      WorldVec2::new(bounds.max.x, y),
+      if i == 0 { axis_color } else { color },
+      win_info,
+      aspect_ratio,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn unit(value: f32) -> WorldUnit {
+    WorldUnit::new_unchecked(value)
+  }
+
+  #[test]
+  fn fill_lines_tile_without_bleeding() {
+    let rect = Rect::new(-5., -2., 5., 2.);
+    assert_eq!(
+      fill_lines(rect, 2.),
+      [
+        (Vec2::new(-5., -1.), Vec2::new(5., -1.)),
+        (Vec2::new(-5., 1.), Vec2::new(5., 1.)),
+      ]
+    );
+    assert_eq!(
+      fill_lines(Rect::new(0., 0., 3., 10.), 2.),
+      [
+        (Vec2::new(1., 0.), Vec2::new(1., 10.)),
+        (Vec2::new(2., 0.), Vec2::new(2., 10.)),
+      ]
+    );
+  }
+
+  #[test]
+  fn fill_lines_center_rects_thinner_than_a_line() {
+    assert_eq!(
+      fill_lines(Rect::new(0., 0., 1., 10.), 2.),
+      [(Vec2::new(0.5, 0.), Vec2::new(0.5, 10.))]
+    );
+  }
+
+  #[test]
+  fn fill_lines_skip_huge_rects() {
+    assert_eq!(fill_lines(Rect::new(-1e12, -1e12, 1e12, 1e12), 2.), []);
+    assert_eq!(fill_lines(Rect::new(0., 0., 100., 1e12), 0.).len(), 100);
+  }
+
+  #[test]
+  fn filled_world_rect_covers_its_pixel_bounds() {
+    let win_info = WinInfo::new(100., 100.);
+    let aspect_ratio = AspectRatio::new(1.);
+    let (center, size) = pixel_bounds(
+      WorldVec2::new(unit(5.), unit(-10.)),
+      &WorldRect::new(unit(10.), unit(5.)),
+      &win_info,
+      &aspect_ratio,
+    );
+    assert_eq!((center, size), (Vec2::new(10., -20.), Vec2::new(20., 10.)));
+    let lines = fill_lines(Rect::from_center_size(center, size), 2.);
+    assert_eq!(
+      lines.first(),
+      Some(&(Vec2::new(0., -24.), Vec2::new(20., -24.)))
+    );
+    assert_eq!(
+      lines.last(),
+      Some(&(Vec2::new(0., -16.), Vec2::new(20., -16.)))
+    );
+    assert_eq!(lines.len(), 5);
+  }
+
+  #[test]
+  fn grid_line_indices_cover_bounds() {
+    assert_eq!(
+      grid_line_indices(unit(-2.5), unit(2.5), unit(1.)),
+      Some(-2..=2)
+    );
+    assert_eq!(
+      grid_line_indices(unit(0.5), unit(4.), unit(2.)),
+      Some(1..=2)
+    );
+  }
+
+  #[test]
+  fn grid_line_indices_skip_too_many_lines() {
+    assert!(grid_line_indices(unit(-1e9), unit(1e9), unit(1.)).is_none());
+    assert!(grid_line_indices(unit(f32::MIN), unit(f32::MAX), unit(1e-3)).is_none());
+  }
+
+  #[test]
+  fn letterbox_bars_cover_window_outside_world() {
+    let aspect_ratio = AspectRatio::from_window_size(1280., 720.);
+    assert_eq!(
+      letterbox_bars(&WinInfo::new(1600., 720.), &aspect_ratio),
+      [
+        Rect::new(-800., -360., -640., 360.),
+        Rect::new(640., -360., 800., 360.),
+      ]
+    );
+    assert_eq!(
+      letterbox_bars(&WinInfo::new(1280., 900.), &aspect_ratio),
+      [
+        Rect::new(-640., -450., 640., -360.),
+        Rect::new(-640., 360., 640., 450.),
+      ]
+    );
+    assert!(
+      letterbox_bars(&WinInfo::new(640., 360.), &aspect_ratio)
+        .iter()
+        .all(Rect::is_empty)
+    );
+  }
+}
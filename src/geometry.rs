@@ -0,0 +1,69 @@
+use crate::world_unit::WorldVec2;
+
+/// Returns the point where segment `a0`-`a1` crosses segment `b0`-`b1`, including when they only
+/// touch at an endpoint. Parallel segments, including collinear overlapping ones, have no single
+/// intersection point and return `None`.
+pub fn segment_intersection(
+  a0: WorldVec2,
+  a1: WorldVec2,
+  b0: WorldVec2,
+  b1: WorldVec2,
+) -> Option<WorldVec2> {
+  let a = a1 - a0;
+  let b = b1 - b0;
+  let denom = a.perp_dot(b);
+  if denom == 0. {
+    return None;
+  }
+
+  let offset = b0 - a0;
+  let t = offset.perp_dot(b) / denom;
+  let u = offset.perp_dot(a) / denom;
+  ((0. ..=1.).contains(&t) && (0. ..=1.).contains(&u)).then(|| a0 + a * t)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::world_unit::WorldUnit;
+
+  fn vec2(x: f32, y: f32) -> WorldVec2 {
+    WorldVec2::new(WorldUnit::new_unchecked(x), WorldUnit::new_unchecked(y))
+  }
+
+  #[test]
+  fn crossing_segments_intersect() {
+    assert_eq!(
+      segment_intersection(vec2(0., 0.), vec2(4., 4.), vec2(0., 4.), vec2(4., 0.)),
+      Some(vec2(2., 2.))
+    );
+    assert_eq!(
+      segment_intersection(vec2(0., 0.), vec2(1., 1.), vec2(0., 4.), vec2(4., 0.)),
+      None
+    );
+  }
+
+  #[test]
+  fn parallel_and_collinear_segments_do_not_intersect() {
+    assert_eq!(
+      segment_intersection(vec2(0., 0.), vec2(4., 0.), vec2(0., 1.), vec2(4., 1.)),
+      None
+    );
+    assert_eq!(
+      segment_intersection(vec2(0., 0.), vec2(4., 0.), vec2(2., 0.), vec2(6., 0.)),
+      None
+    );
+  }
+
+  #[test]
+  fn segments_touching_at_endpoint_intersect() {
+    assert_eq!(
+      segment_intersection(vec2(0., 0.), vec2(2., 0.), vec2(2., 0.), vec2(2., 3.)),
+      Some(vec2(2., 0.))
+    );
+    assert_eq!(
+      segment_intersection(vec2(0., 0.), vec2(2., 2.), vec2(1., 1.), vec2(3., -1.)),
+      Some(vec2(1., 1.))
+    );
+  }
+}
@@ -1,5 +1,8 @@
 use bevy::app::plugin_group;
 
+#[cfg(feature = "debug_draw")]
+pub mod debug_draw;
+pub mod geometry;
 pub mod mouse;
 pub mod position;
 pub mod win_info;
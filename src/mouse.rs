@@ -1,54 +1,592 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Duration,
+};
+
 use bevy::{
   app::{App, Plugin, PreUpdate},
   ecs::{
-    event::{Event, EventWriter},
+    event::{Event, EventReader, EventWriter},
     query::With,
-    system::{Res, Single},
+    schedule::{
+      IntoSystemConfigs, IntoSystemSetConfigs, SystemSet,
+      common_conditions::{not, resource_exists},
+    },
+    system::{Local, Res, ResMut, Resource, Single, SystemParam},
   },
-  input::{ButtonInput, mouse::MouseButton},
+  input::{ButtonInput, InputSystem, mouse::MouseButton},
+  math::Vec2,
+  time::Time,
   window::{PrimaryWindow, Window},
 };
 
 use crate::{
   win_info::WinInfo,
-  world_unit::{AspectRatio, WorldVec2},
+  world_unit::{AspectRatio, WorldUnit, WorldVec2},
 };
 
-#[derive(Event)]
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
 pub enum MouseEvent {
+  /// A press of the `MouseBindings::primary` button, which is left click by default.
   LeftClick(WorldVec2),
+  /// A press of the `MouseBindings::secondary` button, which is right click by default.
   RightClick(WorldVec2),
+  /// The second of two presses of the same button in quick succession, per `DoubleClickSettings`.
+  DoubleClick { button: MouseButton, pos: WorldVec2 },
+  /// The cursor moved into the window.
+  CursorEntered,
+  /// The cursor moved out of the window.
+  CursorLeft,
+}
+
+/// Systems that emit `MouseEvent`s. Order against `MouseSystems::ProcessInput` to read events in
+/// the same frame they are produced.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseSystems {
+  ProcessInput,
+}
+
+/// Set to true while UI is handling the pointer (e.g. the cursor is over a panel), to stop
+/// `MousePlugin` from emitting any `MouseEvent`s that frame.
+#[derive(Resource, Default)]
+pub struct PointerCaptured(pub bool);
+
+/// Which physical buttons produce `MouseEvent::LeftClick` and `MouseEvent::RightClick`. If both are
+/// the same button, it produces only `LeftClick`.
+#[derive(Resource)]
+pub struct MouseBindings {
+  pub primary: MouseButton,
+  pub secondary: MouseButton,
+}
+
+impl Default for MouseBindings {
+  fn default() -> Self {
+    Self {
+      primary: MouseButton::Left,
+      secondary: MouseButton::Right,
+    }
+  }
+}
+
+#[derive(Resource)]
+pub struct DoubleClickSettings {
+  /// The longest time between two presses that still counts as a double click.
+  pub interval: Duration,
+  /// The farthest the cursor may move between two presses that still counts as a double click.
+  pub radius: WorldUnit,
+  /// If true, the second press of a double click emits only `DoubleClick`, not a single click.
+  pub suppress_single_clicks: bool,
+}
+
+impl Default for DoubleClickSettings {
+  fn default() -> Self {
+    Self {
+      interval: Duration::from_millis(300),
+      radius: WorldUnit::ONE * 0.5,
+      suppress_single_clicks: false,
+    }
+  }
+}
+
+/// What `process_input` remembers between frames: whether the cursor was in the window, and the
+/// last press of each button for double click detection.
+#[derive(Default)]
+pub struct MouseState {
+  cursor_in_window: bool,
+  last_presses: HashMap<MouseButton, (Duration, WorldVec2)>,
+}
+
+/// When inserted, records every emitted `MouseEvent` along with the elapsed time at which it was
+/// emitted.
+#[derive(Resource, Default)]
+pub struct MouseEventRecorder {
+  events: Vec<(Duration, MouseEvent)>,
+}
+
+impl MouseEventRecorder {
+  pub fn events(&self) -> &[(Duration, MouseEvent)] {
+    &self.events
+  }
+
+  pub fn take(&mut self) -> Vec<(Duration, MouseEvent)> {
+    std::mem::take(&mut self.events)
+  }
+}
+
+/// When inserted, `MousePlugin` stops reading real input and instead emits each scripted event once
+/// the elapsed time reaches its timestamp. Pairs with the output of `MouseEventRecorder`.
+#[derive(Resource)]
+pub struct MouseEventReplay {
+  events: VecDeque<(Duration, MouseEvent)>,
+}
+
+impl MouseEventReplay {
+  pub fn new(events: impl IntoIterator<Item = (Duration, MouseEvent)>) -> Self {
+    let mut events: Vec<_> = events.into_iter().collect();
+    events.sort_by_key(|(timestamp, _)| *timestamp);
+    Self { events: events.into() }
+  }
+
+  pub fn is_finished(&self) -> bool {
+    self.events.is_empty()
+  }
+}
+
+/// The settings `process_input` converts input with.
+pub struct MouseInputConfig<'a> {
+  pub win_info: &'a WinInfo,
+  pub aspect_ratio: &'a AspectRatio,
+  pub bindings: &'a MouseBindings,
+  pub double_click_settings: &'a DoubleClickSettings,
+}
+
+/// Turns one frame of raw mouse input into `MouseEvent`s. `cursor` is the cursor position in window
+/// coordinates, if it is in the window, and `now` is the elapsed time used to detect double clicks.
+/// Runs without an `App`, so input handling can be tested headlessly.
+pub fn process_input(
+  cursor: Option<Vec2>,
+  buttons: &ButtonInput<MouseButton>,
+  now: Duration,
+  config: &MouseInputConfig,
+  state: &mut MouseState,
+) -> Vec<MouseEvent> {
+  let mut mouse_events = Vec::new();
+  if cursor.is_some() != state.cursor_in_window {
+    state.cursor_in_window = cursor.is_some();
+    mouse_events.push(if state.cursor_in_window {
+      MouseEvent::CursorEntered
+    } else {
+      MouseEvent::CursorLeft
+    });
+  }
+
+  let Some(cursor) = cursor else {
+    return mouse_events;
+  };
+
+  let pos = WorldVec2::from_window_screen_pos(cursor, config.win_info, config.aspect_ratio);
+  let settings = config.double_click_settings;
+  let bindings = config.bindings;
+  let clicks = [
+    (bindings.primary, MouseEvent::LeftClick(pos)),
+    (bindings.secondary, MouseEvent::RightClick(pos)),
+  ];
+  // Processing a button bound to both twice would make its one press look like a double click.
+  let clicks = if bindings.primary == bindings.secondary {
+    &clicks[..1]
+  } else {
+    &clicks[..]
+  };
+  for &(button, click) in clicks {
+    if !buttons.just_pressed(button) {
+      continue;
+    }
+
+    let is_double_click =
+      state
+        .last_presses
+        .remove(&button)
+        .is_some_and(|(last_time, last_pos)| {
+          now.saturating_sub(last_time) <= settings.interval
+            && last_pos.distance(pos) <= settings.radius
+        });
+    if !is_double_click {
+      state.last_presses.insert(button, (now, pos));
+    }
+
+    if !(is_double_click && settings.suppress_single_clicks) {
+      mouse_events.push(click);
+    }
+    if is_double_click {
+      mouse_events.push(MouseEvent::DoubleClick { button, pos });
+    }
+  }
+  mouse_events
+}
+
+/// Everything `MousePlugin::handle_input` reads to call `process_input`.
+#[derive(SystemParam)]
+struct MouseInput<'w, 's> {
+  win_info: Res<'w, WinInfo>,
+  aspect_ratio: Res<'w, AspectRatio>,
+  time: Res<'w, Time>,
+  bindings: Res<'w, MouseBindings>,
+  double_click_settings: Res<'w, DoubleClickSettings>,
+  buttons: Res<'w, ButtonInput<MouseButton>>,
+  window: Single<'w, &'static Window, With<PrimaryWindow>>,
+  state: Local<'s, MouseState>,
 }
 
 #[derive(Default)]
 pub(crate) struct MousePlugin;
 
 impl MousePlugin {
-  fn handle_input(
-    win_info: Res<WinInfo>,
-    aspect_ratio: Res<AspectRatio>,
-    mut mouse_events: EventWriter<MouseEvent>,
-    buttons: Res<ButtonInput<MouseButton>>,
-    window: Single<&Window, With<PrimaryWindow>>,
-  ) {
-    let Some(cursor_pos) = window.cursor_position() else {
-      return;
+  fn handle_input(mut input: MouseInput, mut mouse_events: EventWriter<MouseEvent>) {
+    let config = MouseInputConfig {
+      win_info: &input.win_info,
+      aspect_ratio: &input.aspect_ratio,
+      bindings: &input.bindings,
+      double_click_settings: &input.double_click_settings,
     };
+    mouse_events.send_batch(process_input(
+      input.window.cursor_position(),
+      &input.buttons,
+      input.time.elapsed(),
+      &config,
+      &mut input.state,
+    ));
+  }
 
-    let pos = WorldVec2::from_window_screen_pos(cursor_pos, &win_info, &aspect_ratio);
-    if buttons.just_pressed(MouseButton::Left) {
-      mouse_events.send(MouseEvent::LeftClick(pos));
-    }
-    if buttons.just_pressed(MouseButton::Right) {
-      mouse_events.send(MouseEvent::RightClick(pos));
+  fn replay_events(
+    time: Res<Time>,
+    mut replay: ResMut<MouseEventReplay>,
+    mut mouse_events: EventWriter<MouseEvent>,
+  ) {
+    while replay
+      .events
+      .front()
+      .is_some_and(|(timestamp, _)| *timestamp <= time.elapsed())
+    {
+      if let Some((_, event)) = replay.events.pop_front() {
+        mouse_events.send(event);
+      }
     }
   }
+
+  fn record_events(
+    time: Res<Time>,
+    mut recorder: ResMut<MouseEventRecorder>,
+    mut mouse_events: EventReader<MouseEvent>,
+  ) {
+    recorder
+      .events
+      .extend(mouse_events.read().map(|event| (time.elapsed(), *event)));
+  }
 }
 
 impl Plugin for MousePlugin {
   fn build(&self, app: &mut App) {
     app
-      .add_systems(PreUpdate, MousePlugin::handle_input)
+      .init_resource::<PointerCaptured>()
+      .init_resource::<MouseBindings>()
+      .init_resource::<DoubleClickSettings>()
+      .configure_sets(PreUpdate, MouseSystems::ProcessInput.after(InputSystem))
+      .add_systems(
+        PreUpdate,
+        (
+          MousePlugin::handle_input
+            .run_if(not(resource_exists::<MouseEventReplay>))
+            .run_if(|captured: Res<PointerCaptured>| !captured.0),
+          MousePlugin::replay_events.run_if(resource_exists::<MouseEventReplay>),
+          MousePlugin::record_events.run_if(resource_exists::<MouseEventRecorder>),
+        )
+          .chain()
+          .in_set(MouseSystems::ProcessInput),
+      )
       .add_event::<MouseEvent>();
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_app() -> App {
+    let mut app = App::new();
+    app
+      .insert_resource(WinInfo::new(100., 100.))
+      .insert_resource(AspectRatio::new(1.))
+      .init_resource::<Time>()
+      .init_resource::<MouseEventRecorder>()
+      .add_plugins(MousePlugin);
+    app
+  }
+
+  /// Adds a primary window and mouse button input, as `DefaultPlugins` would.
+  fn with_window(mut app: App) -> App {
+    app.init_resource::<ButtonInput<MouseButton>>();
+    app.world_mut().spawn((Window::default(), PrimaryWindow));
+    app
+  }
+
+  fn set_cursor(app: &mut App, cursor: Option<Vec2>) {
+    let mut windows = app.world_mut().query::<&mut Window>();
+    windows
+      .single_mut(app.world_mut())
+      .set_cursor_position(cursor);
+  }
+
+  fn press(app: &mut App, button: MouseButton) {
+    app
+      .world_mut()
+      .resource_mut::<ButtonInput<MouseButton>>()
+      .press(button);
+  }
+
+  /// Runs a frame at `elapsed`, then releases all buttons.
+  fn advance_to(app: &mut App, elapsed: Duration) {
+    app.world_mut().resource_mut::<Time>().advance_to(elapsed);
+    app.update();
+    if let Some(mut buttons) = app
+      .world_mut()
+      .get_resource_mut::<ButtonInput<MouseButton>>()
+    {
+      buttons.reset_all();
+    }
+  }
+
+  fn recorded(app: &mut App) -> Vec<(Duration, MouseEvent)> {
+    app.world_mut().resource_mut::<MouseEventRecorder>().take()
+  }
+
+  #[test]
+  fn replay_emits_events_once_due() {
+    let click = MouseEvent::LeftClick(WorldVec2::ZERO);
+    let right_click = MouseEvent::RightClick(WorldVec2::ZERO);
+    let mut app = test_app();
+    app.insert_resource(MouseEventReplay::new([
+      (Duration::from_millis(20), right_click),
+      (Duration::from_millis(10), click),
+    ]));
+
+    advance_to(&mut app, Duration::from_millis(5));
+    assert_eq!(recorded(&mut app), []);
+
+    advance_to(&mut app, Duration::from_millis(20));
+    assert_eq!(
+      recorded(&mut app),
+      [
+        (Duration::from_millis(20), click),
+        (Duration::from_millis(20), right_click),
+      ]
+    );
+    assert!(app.world().resource::<MouseEventReplay>().is_finished());
+  }
+
+  #[test]
+  fn replaying_a_recording_reproduces_its_events() {
+    let mut app = with_window(test_app());
+    set_cursor(&mut app, Some(Vec2::new(75., 25.)));
+    press(&mut app, MouseButton::Left);
+    advance_to(&mut app, Duration::from_millis(10));
+    press(&mut app, MouseButton::Right);
+    advance_to(&mut app, Duration::from_millis(20));
+    set_cursor(&mut app, None);
+    advance_to(&mut app, Duration::from_millis(30));
+    let recording = recorded(&mut app);
+    assert_eq!(recording.len(), 4);
+
+    let mut replay_app = test_app();
+    replay_app.insert_resource(MouseEventReplay::new(recording.clone()));
+    for elapsed in [10, 20, 30] {
+      advance_to(&mut replay_app, Duration::from_millis(elapsed));
+    }
+    assert_eq!(recorded(&mut replay_app), recording);
+  }
+
+  #[test]
+  fn systems_after_process_input_see_events_same_frame() {
+    #[derive(Resource, Default)]
+    struct Seen(Vec<MouseEvent>);
+
+    let mut app = with_window(test_app());
+    app.init_resource::<Seen>().add_systems(
+      PreUpdate,
+      (|mut events: EventReader<MouseEvent>, mut seen: ResMut<Seen>| {
+        seen.0.extend(events.read().copied());
+      })
+      .after(MouseSystems::ProcessInput),
+    );
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    press(&mut app, MouseButton::Left);
+    advance_to(&mut app, Duration::ZERO);
+
+    assert_eq!(
+      app.world().resource::<Seen>().0,
+      [
+        MouseEvent::CursorEntered,
+        MouseEvent::LeftClick(WorldVec2::ZERO),
+      ]
+    );
+  }
+
+  fn recorded_events(app: &mut App) -> Vec<MouseEvent> {
+    recorded(app).into_iter().map(|(_, event)| event).collect()
+  }
+
+  #[test]
+  fn cursor_leaving_and_reentering_emits_events_once() {
+    let mut app = with_window(test_app());
+    advance_to(&mut app, Duration::ZERO);
+    assert_eq!(recorded_events(&mut app), []);
+
+    set_cursor(&mut app, Some(Vec2::new(10., 10.)));
+    advance_to(&mut app, Duration::ZERO);
+    advance_to(&mut app, Duration::ZERO);
+    assert_eq!(recorded_events(&mut app), [MouseEvent::CursorEntered]);
+
+    set_cursor(&mut app, None);
+    advance_to(&mut app, Duration::ZERO);
+    advance_to(&mut app, Duration::ZERO);
+    assert_eq!(recorded_events(&mut app), [MouseEvent::CursorLeft]);
+
+    set_cursor(&mut app, Some(Vec2::new(20., 10.)));
+    advance_to(&mut app, Duration::ZERO);
+    assert_eq!(recorded_events(&mut app), [MouseEvent::CursorEntered]);
+  }
+
+  fn click_at(app: &mut App, button: MouseButton, elapsed_ms: u64) -> Vec<MouseEvent> {
+    press(app, button);
+    advance_to(app, Duration::from_millis(elapsed_ms));
+    recorded_events(app)
+  }
+
+  #[test]
+  fn quick_presses_emit_double_click() {
+    let mut app = with_window(test_app());
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    advance_to(&mut app, Duration::ZERO);
+    recorded(&mut app);
+
+    let click = MouseEvent::LeftClick(WorldVec2::ZERO);
+    let double_click = MouseEvent::DoubleClick {
+      button: MouseButton::Left,
+      pos: WorldVec2::ZERO,
+    };
+    assert_eq!(click_at(&mut app, MouseButton::Left, 1000), [click]);
+    assert_eq!(
+      click_at(&mut app, MouseButton::Left, 1200),
+      [click, double_click]
+    );
+    assert_eq!(click_at(&mut app, MouseButton::Left, 1300), [click]);
+    assert_eq!(click_at(&mut app, MouseButton::Left, 2000), [click]);
+
+    app
+      .world_mut()
+      .resource_mut::<DoubleClickSettings>()
+      .suppress_single_clicks = true;
+    assert_eq!(click_at(&mut app, MouseButton::Left, 2100), [double_click]);
+  }
+
+  #[test]
+  fn presses_far_apart_are_not_double_clicks() {
+    let mut app = with_window(test_app());
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    advance_to(&mut app, Duration::ZERO);
+    recorded(&mut app);
+
+    click_at(&mut app, MouseButton::Left, 1000);
+    set_cursor(&mut app, Some(Vec2::new(60., 50.)));
+    assert_eq!(
+      click_at(&mut app, MouseButton::Left, 1100),
+      [MouseEvent::LeftClick(WorldVec2::X * 5.)]
+    );
+  }
+
+  #[test]
+  fn press_before_last_press_does_not_panic() {
+    let mut app = with_window(test_app());
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    advance_to(&mut app, Duration::ZERO);
+    recorded(&mut app);
+
+    click_at(&mut app, MouseButton::Left, 1000);
+    // Restarting the clock puts the next press before the last one.
+    app.insert_resource(Time::<()>::default());
+    assert_eq!(
+      click_at(&mut app, MouseButton::Left, 500),
+      [
+        MouseEvent::LeftClick(WorldVec2::ZERO),
+        MouseEvent::DoubleClick {
+          button: MouseButton::Left,
+          pos: WorldVec2::ZERO,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn process_input_converts_clicks_to_world_space() {
+    let mut buttons = ButtonInput::default();
+    buttons.press(MouseButton::Left);
+    let mut state = MouseState::default();
+    let config = MouseInputConfig {
+      win_info: &WinInfo::new(100., 100.),
+      aspect_ratio: &AspectRatio::new(1.),
+      bindings: &MouseBindings::default(),
+      double_click_settings: &DoubleClickSettings::default(),
+    };
+    let events = process_input(
+      Some(Vec2::new(75., 25.)),
+      &buttons,
+      Duration::ZERO,
+      &config,
+      &mut state,
+    );
+    let quarter = WorldUnit::right(&AspectRatio::new(1.)) / 2.;
+    assert_eq!(
+      events,
+      [
+        MouseEvent::CursorEntered,
+        MouseEvent::LeftClick(WorldVec2::new(quarter, quarter)),
+      ]
+    );
+  }
+
+  #[test]
+  fn swapped_bindings_swap_click_events() {
+    let mut app = with_window(test_app());
+    app.insert_resource(MouseBindings {
+      primary: MouseButton::Right,
+      secondary: MouseButton::Left,
+    });
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    advance_to(&mut app, Duration::ZERO);
+    recorded(&mut app);
+
+    assert_eq!(
+      click_at(&mut app, MouseButton::Right, 1000),
+      [MouseEvent::LeftClick(WorldVec2::ZERO)]
+    );
+    assert_eq!(
+      click_at(&mut app, MouseButton::Left, 2000),
+      [MouseEvent::RightClick(WorldVec2::ZERO)]
+    );
+    assert_eq!(click_at(&mut app, MouseButton::Middle, 3000), []);
+  }
+
+  #[test]
+  fn button_bound_twice_emits_one_click() {
+    let mut app = with_window(test_app());
+    app.insert_resource(MouseBindings {
+      primary: MouseButton::Left,
+      secondary: MouseButton::Left,
+    });
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    advance_to(&mut app, Duration::ZERO);
+    recorded(&mut app);
+
+    assert_eq!(
+      click_at(&mut app, MouseButton::Left, 1000),
+      [MouseEvent::LeftClick(WorldVec2::ZERO)]
+    );
+  }
+
+  #[test]
+  fn pointer_captured_suppresses_clicks() {
+    let mut app = with_window(test_app());
+    set_cursor(&mut app, Some(Vec2::new(50., 50.)));
+    advance_to(&mut app, Duration::ZERO);
+    recorded(&mut app);
+
+    app.insert_resource(PointerCaptured(true));
+    assert_eq!(click_at(&mut app, MouseButton::Left, 1000), []);
+    assert_eq!(click_at(&mut app, MouseButton::Right, 2000), []);
+
+    app.insert_resource(PointerCaptured(false));
+    assert_eq!(
+      click_at(&mut app, MouseButton::Left, 3000),
+      [MouseEvent::LeftClick(WorldVec2::ZERO)]
+    );
+  }
+}
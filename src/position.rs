@@ -2,25 +2,31 @@ use bevy::{
   app::{App, Plugin, Update},
   ecs::{
     component::Component,
+    entity::Entity,
+    schedule::IntoSystemConfigs,
     system::{Query, Res},
   },
   math::Quat,
+  time::Time,
   transform::components::Transform,
 };
 
 use crate::{
   win_info::WinInfo,
-  world_unit::{AspectRatio, WorldUnit, WorldVec2},
+  world_init::WorldCamera,
+  world_unit::{AspectRatio, WorldRect, WorldUnit, WorldVec2},
 };
 
 #[derive(Component, Default)]
 #[require(Transform)]
 pub struct Position {
-  /// The position of the center of this entity, where (0, 0) is the center of the screen.
+  /// The position of the center of this entity, where (0, 0) is the center of the screen while the
+  /// `WorldCamera` is at the origin.
   pub pos: WorldVec2,
   /// The scaled width of the image in terms of world units.
   pub scale: WorldUnit,
-  /// The original width of the image in pixels.
+  /// The original width of the image in pixels. Zero, as in a defaulted `Position`, is treated as
+  /// one.
   pub image_width: u32,
   pub rotation: Quat,
   /// Z-idx, which controls render priority (higher priorty is drawn on top of lower priority).
@@ -37,33 +43,289 @@ impl Position {
       z_idx,
     }
   }
+
+  /// Writes this position into the render `transform` for the given window, as `PositionPlugin`
+  /// does every frame.
+  pub fn sync_transform(
+    &self,
+    transform: &mut Transform,
+    win_info: &WinInfo,
+    aspect_ratio: &AspectRatio,
+  ) {
+    let Self { pos, scale, image_width, rotation, z_idx } = self;
+    let image_width = (*image_width).max(1) as f32;
+
+    pos.apply_to_transform(transform, win_info, aspect_ratio);
+    transform.translation.z = *z_idx;
+    transform.scale.x = scale.to_x(win_info, aspect_ratio) / image_width;
+    transform.scale.y = scale.to_y(win_info, aspect_ratio) / image_width;
+    transform.rotation = *rotation;
+  }
+}
+
+/// Velocity in world units per second, integrated into `Position` every frame by `PositionPlugin`.
+#[derive(Component, Clone, Copy, Debug, Default)]
+#[require(Position)]
+pub struct WorldVelocity(pub WorldVec2);
+
+/// Keeps an entity's `Position` on screen every frame, such that a box of `half_extent` around it
+/// (e.g. half its sprite size) remains fully visible through the `WorldCamera`, or a camera at the
+/// origin if there is none or there are several.
+#[derive(Component, Clone, Copy, Debug, Default)]
+#[require(Position)]
+pub struct KeepOnScreen {
+  pub half_extent: WorldVec2,
+}
+
+/// Returns every entity whose bounds, centered at its `Position`, overlap `selection` centered at
+/// `selection_center`.
+pub fn entities_in_rect(
+  selection_center: WorldVec2,
+  selection: &WorldRect,
+  query: &Query<(Entity, &Position, &WorldRect)>,
+) -> Vec<Entity> {
+  query
+    .iter()
+    .filter(|(_, position, rect)| selection.intersects(selection_center, rect, position.pos))
+    .map(|(entity, _, _)| entity)
+    .collect()
 }
 
 #[derive(Default)]
 pub struct PositionPlugin;
 
 impl PositionPlugin {
+  fn integrate_velocities(time: Res<Time>, mut query: Query<(&WorldVelocity, &mut Position)>) {
+    for (WorldVelocity(velocity), mut position) in &mut query {
+      position.pos += *velocity * time.delta_secs();
+    }
+  }
+
+  fn keep_on_screen(
+    aspect_ratio: Res<AspectRatio>,
+    cameras: Query<&WorldCamera>,
+    mut query: Query<(&KeepOnScreen, &mut Position)>,
+  ) {
+    let camera = WorldCamera::single_or_origin(&cameras);
+    let screen = camera.visible_world(&aspect_ratio);
+    for (KeepOnScreen { half_extent }, mut position) in &mut query {
+      position.pos = position.pos.clamp_to_bounds(&screen, *half_extent);
+    }
+  }
+
   fn sync_render_positions(
     win_info: Res<WinInfo>,
     aspect_ratio: Res<AspectRatio>,
     mut query: Query<(&Position, &mut Transform)>,
   ) {
-    for (Position { pos, scale, image_width, rotation, z_idx }, mut transform) in &mut query {
-      let pos = pos.to_absolute(&win_info, &aspect_ratio);
-      let image_width = *image_width as f32;
-
-      transform.translation.x = pos.x;
-      transform.translation.y = pos.y;
-      transform.translation.z = *z_idx;
-      transform.scale.x = scale.to_x(&win_info, &aspect_ratio) / image_width;
-      transform.scale.y = scale.to_y(&win_info, &aspect_ratio) / image_width;
-      transform.rotation = *rotation;
+    for (position, mut transform) in &mut query {
+      position.sync_transform(&mut transform, &win_info, &aspect_ratio);
     }
   }
 }
 
 impl Plugin for PositionPlugin {
   fn build(&self, app: &mut App) {
-    app.add_systems(Update, PositionPlugin::sync_render_positions);
+    app.add_systems(
+      Update,
+      (
+        PositionPlugin::integrate_velocities,
+        PositionPlugin::keep_on_screen,
+        PositionPlugin::sync_render_positions,
+      )
+        .chain(),
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use bevy::{
+    ecs::{system::RunSystemOnce, world::World},
+    math::{Vec2, Vec3},
+  };
+
+  use super::*;
+
+  fn unit(value: f32) -> WorldUnit {
+    WorldUnit::ONE * value
+  }
+
+  fn test_app() -> App {
+    let mut app = App::new();
+    app
+      .insert_resource(WinInfo::new(100., 100.))
+      .insert_resource(AspectRatio::new(1.).with_units_per_screen_max(10.))
+      .init_resource::<Time>()
+      .add_plugins(PositionPlugin);
+    app
+  }
+
+  #[test]
+  fn entities_in_rect_returns_overlapping_entities() {
+    let mut world = World::new();
+    let mut spawn = |x: f32, y: f32| {
+      world
+        .spawn((
+          Position::new(WorldVec2::new(unit(x), unit(y)), WorldUnit::ONE, 1, 0.),
+          WorldRect::new(unit(2.), unit(2.)),
+        ))
+        .id()
+    };
+    let inside = spawn(0., 0.);
+    let overlapping_edge = spawn(2.5, 0.);
+    let touching_corner = spawn(-3., 2.);
+    spawn(5., 0.);
+    spawn(0., -4.);
+
+    let selection = WorldRect::new(unit(4.), unit(2.));
+    let mut selected = world
+      .run_system_once(move |query: Query<(Entity, &Position, &WorldRect)>| {
+        entities_in_rect(WorldVec2::ZERO, &selection, &query)
+      })
+      .unwrap();
+    selected.sort();
+    assert_eq!(selected, [inside, overlapping_edge, touching_corner]);
+  }
+
+  #[test]
+  fn position_at_right_edge_sits_at_visible_screen_edge() {
+    let aspect_ratio = AspectRatio::from_window_size(1280., 720.);
+    let position = Position::new(
+      WorldVec2::new(
+        WorldUnit::right(&aspect_ratio),
+        WorldUnit::top(&aspect_ratio),
+      ),
+      WorldUnit::ONE,
+      1,
+      0.,
+    );
+    let mut app = App::new();
+    app
+      .insert_resource(WinInfo::new(1280., 720.))
+      .insert_resource(aspect_ratio)
+      .init_resource::<Time>()
+      .add_plugins(PositionPlugin);
+    let entity = app.world_mut().spawn(position).id();
+    let translation = |app: &App| {
+      let transform = app.world().get::<Transform>(entity).unwrap();
+      transform.translation.truncate()
+    };
+
+    app.update();
+    assert_eq!(translation(&app), Vec2::new(640., 360.));
+
+    // A window wider than the world letterboxes the sides, so the edge is inside the window.
+    app.insert_resource(WinInfo::new(1600., 720.));
+    app.update();
+    assert_eq!(translation(&app), Vec2::new(640., 360.));
+  }
+
+  #[test]
+  fn sync_transform_runs_without_an_app() {
+    let win_info = WinInfo::new(100., 100.);
+    let aspect_ratio = AspectRatio::new(1.).with_units_per_screen_max(10.);
+    let position = Position::new(WorldVec2::new(unit(1.), unit(-2.)), unit(2.), 10, 3.);
+    let mut transform = Transform::default();
+    position.sync_transform(&mut transform, &win_info, &aspect_ratio);
+    assert_eq!(transform.translation, Vec3::new(10., -20., 3.));
+    assert_eq!(transform.scale.truncate(), Vec2::splat(2.));
+  }
+
+  #[test]
+  fn required_position_syncs_a_finite_scale() {
+    let mut app = test_app();
+    let entities = [
+      app.world_mut().spawn(WorldVelocity::default()).id(),
+      app.world_mut().spawn(KeepOnScreen::default()).id(),
+    ];
+    app.update();
+    for entity in entities {
+      let transform = app.world().get::<Transform>(entity).unwrap();
+      assert!(transform.scale.is_finite(), "{:?}", transform.scale);
+    }
+  }
+
+  #[test]
+  fn velocity_integrates_into_position() {
+    let mut app = test_app();
+    let entity = app
+      .world_mut()
+      .spawn(WorldVelocity(WorldVec2::new(unit(2.), unit(-1.))))
+      .id();
+    for _ in 0..4 {
+      app
+        .world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_millis(500));
+      app.update();
+    }
+    assert_eq!(
+      app.world().get::<Position>(entity).unwrap().pos,
+      WorldVec2::new(unit(4.), unit(-2.))
+    );
+  }
+
+  #[test]
+  fn keep_on_screen_clamps_with_half_extent() {
+    let mut app = test_app();
+    let entity = app
+      .world_mut()
+      .spawn((
+        Position::new(WorldVec2::new(unit(20.), unit(-20.)), unit(1.), 1, 0.),
+        KeepOnScreen {
+          half_extent: WorldVec2::new(unit(1.), unit(2.)),
+        },
+      ))
+      .id();
+
+    app.update();
+    assert_eq!(
+      app.world().get::<Position>(entity).unwrap().pos,
+      WorldVec2::new(unit(4.), unit(-3.))
+    );
+  }
+
+  #[test]
+  fn keep_on_screen_follows_world_camera() {
+    let mut app = test_app();
+    app
+      .world_mut()
+      .spawn(WorldCamera { pos: WorldVec2::new(unit(20.), unit(0.)) });
+    let entity = app
+      .world_mut()
+      .spawn(KeepOnScreen { half_extent: WorldVec2::splat(unit(1.)) })
+      .id();
+
+    app.update();
+    assert_eq!(
+      app.world().get::<Position>(entity).unwrap().pos,
+      WorldVec2::new(unit(16.), unit(0.))
+    );
+  }
+
+  #[test]
+  fn keep_on_screen_uses_origin_with_several_world_cameras() {
+    let mut app = test_app();
+    for x in [20., -20.] {
+      app
+        .world_mut()
+        .spawn(WorldCamera { pos: WorldVec2::new(unit(x), unit(0.)) });
+    }
+    let entity = app
+      .world_mut()
+      .spawn((
+        Position::new(WorldVec2::new(unit(30.), unit(0.)), unit(1.), 1, 0.),
+        KeepOnScreen { half_extent: WorldVec2::splat(unit(1.)) },
+      ))
+      .id();
+
+    app.update();
+    assert_eq!(
+      app.world().get::<Position>(entity).unwrap().pos,
+      WorldVec2::new(unit(4.), unit(0.))
+    );
   }
 }
@@ -1,6 +1,6 @@
 use bevy::ecs::system::Resource;
 
-#[derive(Resource)]
+#[derive(Resource, Clone, Copy, Debug)]
 pub struct WinInfo {
   pub width: f32,
   pub height: f32,
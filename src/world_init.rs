@@ -1,35 +1,159 @@
+use std::sync::Arc;
+
 #[cfg(not(target_arch = "wasm32"))]
 use bevy::{
   app::AppExit,
-  ecs::{event::EventWriter, system::Res},
   input::{ButtonInput, keyboard::KeyCode},
 };
+#[cfg(feature = "debug_draw")]
+use bevy::{
+  app::Update,
+  color::{
+    Color,
+    palettes::css::{DIM_GRAY, RED},
+  },
+  gizmos::gizmos::Gizmos,
+};
 use bevy::{
-  app::{App, Plugin, PreUpdate, Startup},
+  app::{App, Plugin, PostUpdate, PreUpdate, Startup},
   core_pipeline::core_2d::Camera2d,
   ecs::{
-    event::EventReader,
-    system::{Commands, ResMut},
+    component::Component,
+    event::{Event, EventReader, EventWriter},
+    schedule::IntoSystemConfigs,
+    system::{Commands, EntityCommands, Local, Query, Res, ResMut},
   },
+  transform::{TransformSystem, components::Transform},
   window::WindowResized,
 };
 
-use crate::{win_info::WinInfo, world_unit::AspectRatio};
+#[cfg(feature = "debug_draw")]
+use crate::debug_draw::{WorldGrid, draw_letterbox_bars_at, draw_world_grid, draw_world_overlay};
+use crate::{
+  mouse::MouseSystems,
+  win_info::WinInfo,
+  world_unit::{AspectRatio, WorldAabb, WorldVec2},
+};
+
+/// A camera centered on the world point `pos`. The letterbox bars, grid and `KeepOnScreen` follow
+/// this camera, and assume one at the origin if there is none or there are several.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct WorldCamera {
+  /// `WorldInitPlugin` owns the camera's `Transform`: it writes `pos` into it every frame in
+  /// `PostUpdate`, before transforms propagate, so the camera stays on the same world point when
+  /// the window resizes. Camera-follow systems should move the camera by changing `pos`, as any
+  /// change to its transform is overwritten.
+  pub pos: WorldVec2,
+}
+
+impl WorldCamera {
+  /// Returns the region of the world in view, which is `WorldAabb::screen` moved to `pos`.
+  pub fn visible_world(&self, aspect_ratio: &AspectRatio) -> WorldAabb {
+    let screen = WorldAabb::screen(aspect_ratio);
+    WorldAabb::new(screen.min + self.pos, screen.max + self.pos)
+  }
+
+  /// Returns the only camera in `cameras`, or one at the origin if there is none or there are
+  /// several.
+  pub(crate) fn single_or_origin(cameras: &Query<&WorldCamera>) -> Self {
+    cameras.get_single().copied().unwrap_or_default()
+  }
+}
+
+/// Customizes the camera entity spawned by `WorldInitPlugin`, e.g. by inserting extra components.
+pub type CameraSetup = Arc<dyn Fn(&mut EntityCommands) + Send + Sync>;
+
+/// Sent when a resize changes the window's height / width ratio. The world extent given by
+/// `AspectRatio` stays fixed; only the letterboxed region of the window it maps to changes.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AspectRatioChanged {
+  /// The window's new height / width.
+  pub new: f32,
+}
 
 pub struct WorldInitPlugin {
   pub screen_width: f32,
   pub screen_height: f32,
+  /// How many world units span the longer side of the screen.
+  pub units_per_screen_max: f32,
+  /// Spawns a `Camera2d` on startup. Disable this if you spawn your own camera, and add a
+  /// `WorldCamera` to it if it should move.
+  pub spawn_camera: bool,
+  /// The `WorldCamera::pos` the spawned camera starts at.
+  pub initial_camera_position: WorldVec2,
+  /// Runs on the spawned camera entity after its default components are inserted.
+  pub camera_setup: Option<CameraSetup>,
+  /// Exits the app when Escape is pressed (on non-wasm targets).
+  pub handle_exit: bool,
+  /// If set, covers the parts of the window outside the visible world with this color. Requires the
+  /// `debug_draw` feature and bevy's `GizmoPlugin`, which is part of `DefaultPlugins`.
+  #[cfg(feature = "debug_draw")]
+  pub letterbox_color: Option<Color>,
+  /// Draws the world bounds and origin with gizmos. Ignored in release builds. Requires the
+  /// `debug_draw` feature and bevy's `GizmoPlugin`, which is part of `DefaultPlugins`.
+  #[cfg(feature = "debug_draw")]
+  pub debug_gizmos: bool,
 }
 
 impl Default for WorldInitPlugin {
   fn default() -> Self {
-    Self { screen_width: 1280., screen_height: 720. }
+    Self {
+      screen_width: 1280.,
+      screen_height: 720.,
+      units_per_screen_max: AspectRatio::DEFAULT_UNITS_PER_SCREEN_MAX,
+      spawn_camera: true,
+      initial_camera_position: WorldVec2::ZERO,
+      camera_setup: None,
+      handle_exit: true,
+      #[cfg(feature = "debug_draw")]
+      letterbox_color: None,
+      #[cfg(feature = "debug_draw")]
+      debug_gizmos: false,
+    }
   }
 }
 
 impl WorldInitPlugin {
-  pub fn world_init(mut commands: Commands) {
-    commands.spawn(Camera2d);
+  pub fn with_camera_setup(
+    self,
+    camera_setup: impl Fn(&mut EntityCommands) + Send + Sync + 'static,
+  ) -> Self {
+    Self {
+      camera_setup: Some(Arc::new(camera_setup)),
+      ..self
+    }
+  }
+
+  /// Spawns the camera with bevy's default projection, which maps one transform unit to one pixel
+  /// and tracks the window size. Positions are synced to transforms in pixels via
+  /// `WorldUnit::scale`, which already fits exactly `WorldAabb::screen` into the largest centered
+  /// region of the window, so a sprite at `WorldUnit::right` sits at the visible world edge without
+  /// changing the projection. Custom projections must keep this one-unit-per-pixel mapping.
+  ///
+  /// The camera is tagged with `WorldCamera` centered on `initial_camera_position`, and
+  /// `camera_setup` may add to it.
+  fn world_init(
+    initial_camera_position: WorldVec2,
+    camera_setup: Option<CameraSetup>,
+  ) -> impl Fn(Commands) {
+    move |mut commands| {
+      let mut camera = commands.spawn((Camera2d, WorldCamera { pos: initial_camera_position }));
+      if let Some(camera_setup) = &camera_setup {
+        camera_setup(&mut camera);
+      }
+    }
+  }
+
+  fn sync_camera_transforms(
+    win_info: Res<WinInfo>,
+    aspect_ratio: Res<AspectRatio>,
+    mut cameras: Query<(&WorldCamera, &mut Transform)>,
+  ) {
+    for (camera, mut transform) in &mut cameras {
+      camera
+        .pos
+        .apply_to_transform(&mut transform, &win_info, &aspect_ratio);
+    }
   }
 
   #[cfg(not(target_arch = "wasm32"))]
@@ -45,11 +169,68 @@ impl WorldInitPlugin {
   #[cfg(target_arch = "wasm32")]
   fn app_exit_listener() {}
 
-  fn resize_listener(mut resize_events: EventReader<WindowResized>, mut win_info: ResMut<WinInfo>) {
+  /// Compares against the last finite, positive aspect ratio, so a degenerate size (e.g. 0x0 while
+  /// minimized) neither sends an event nor makes the restore look like a change.
+  fn resize_listener(
+    mut resize_events: EventReader<WindowResized>,
+    mut win_info: ResMut<WinInfo>,
+    mut aspect_ratio_events: EventWriter<AspectRatioChanged>,
+    mut last_aspect_ratio: Local<Option<f32>>,
+  ) {
+    let last_aspect_ratio =
+      last_aspect_ratio.get_or_insert_with(|| win_info.height / win_info.width);
     for e in resize_events.read() {
       win_info.width = e.width;
       win_info.height = e.height;
     }
+
+    let new_aspect_ratio = win_info.height / win_info.width;
+    if new_aspect_ratio.is_finite()
+      && new_aspect_ratio > 0.
+      && new_aspect_ratio != *last_aspect_ratio
+    {
+      *last_aspect_ratio = new_aspect_ratio;
+      aspect_ratio_events.send(AspectRatioChanged { new: new_aspect_ratio });
+    }
+  }
+
+  #[cfg(feature = "debug_draw")]
+  fn draw_letterbox(
+    color: Color,
+  ) -> impl Fn(Gizmos, Res<WinInfo>, Res<AspectRatio>, Query<&WorldCamera>) {
+    move |mut gizmos, win_info, aspect_ratio, cameras| {
+      let camera = WorldCamera::single_or_origin(&cameras);
+      draw_letterbox_bars_at(&mut gizmos, color, camera.pos, &win_info, &aspect_ratio);
+    }
+  }
+
+  #[cfg(feature = "debug_draw")]
+  fn draw_debug_overlay(
+    mut gizmos: Gizmos,
+    win_info: Res<WinInfo>,
+    aspect_ratio: Res<AspectRatio>,
+  ) {
+    draw_world_overlay(&mut gizmos, &win_info, &aspect_ratio);
+  }
+
+  #[cfg(feature = "debug_draw")]
+  fn draw_grid(
+    mut gizmos: Gizmos,
+    grid: Res<WorldGrid>,
+    win_info: Res<WinInfo>,
+    aspect_ratio: Res<AspectRatio>,
+    cameras: Query<&WorldCamera>,
+  ) {
+    let camera = WorldCamera::single_or_origin(&cameras);
+    draw_world_grid(
+      &mut gizmos,
+      grid.spacing,
+      &camera.visible_world(&aspect_ratio),
+      DIM_GRAY,
+      RED,
+      &win_info,
+      &aspect_ratio,
+    );
   }
 }
 
@@ -57,8 +238,290 @@ impl Plugin for WorldInitPlugin {
   fn build(&self, app: &mut App) {
     app
       .insert_resource(WinInfo::new(self.screen_width, self.screen_height))
-      .insert_resource(AspectRatio::new(self.screen_height / self.screen_width))
-      .add_systems(Startup, Self::world_init)
-      .add_systems(PreUpdate, (Self::app_exit_listener, Self::resize_listener));
+      .insert_resource(
+        AspectRatio::from_window_size(self.screen_width, self.screen_height)
+          .with_units_per_screen_max(self.units_per_screen_max),
+      )
+      .add_event::<AspectRatioChanged>()
+      .add_systems(
+        PreUpdate,
+        Self::resize_listener.before(MouseSystems::ProcessInput),
+      )
+      .add_systems(
+        PostUpdate,
+        Self::sync_camera_transforms.before(TransformSystem::TransformPropagate),
+      );
+
+    if self.spawn_camera {
+      app.add_systems(
+        Startup,
+        Self::world_init(self.initial_camera_position, self.camera_setup.clone()),
+      );
+    }
+    if self.handle_exit {
+      app.add_systems(PreUpdate, Self::app_exit_listener);
+    }
+    #[cfg(feature = "debug_draw")]
+    {
+      app.add_systems(
+        Update,
+        Self::draw_grid
+          .run_if(|grid: Option<Res<WorldGrid>>| grid.is_some_and(|grid| grid.enabled)),
+      );
+      if let Some(color) = self.letterbox_color {
+        app.add_systems(Update, Self::draw_letterbox(color));
+      }
+      if self.debug_gizmos && cfg!(debug_assertions) {
+        app.add_systems(Update, Self::draw_debug_overlay);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use bevy::{
+    app::Update,
+    ecs::{entity::Entity, event::Events, query::With, system::Single},
+    input::{ButtonInput, mouse::MouseButton},
+    math::Vec2,
+    time::Time,
+    window::{PrimaryWindow, Window},
+  };
+
+  use super::*;
+  use crate::{
+    mouse::{MouseEvent, MouseEventRecorder, MousePlugin},
+    world_unit::WorldUnit,
+  };
+
+  fn camera_count(app: &mut App) -> usize {
+    app
+      .world_mut()
+      .query::<&Camera2d>()
+      .iter(app.world())
+      .count()
+  }
+
+  #[test]
+  fn spawn_camera_controls_camera_spawning() {
+    for (spawn_camera, cameras) in [(false, 0), (true, 1)] {
+      let mut app = App::new();
+      app
+        .add_event::<WindowResized>()
+        .add_plugins(WorldInitPlugin {
+          spawn_camera,
+          handle_exit: false,
+          ..Default::default()
+        });
+      app.update();
+      assert_eq!(camera_count(&mut app), cameras);
+    }
+  }
+
+  fn plugin_aspect_ratio(plugin: WorldInitPlugin) -> AspectRatio {
+    let mut app = App::new();
+    app.add_plugins(plugin);
+    app.world_mut().remove_resource::<AspectRatio>().unwrap()
+  }
+
+  #[test]
+  fn units_per_screen_max_sets_pixels_per_unit() {
+    let win_info = WinInfo::new(1280., 720.);
+    let aspect_ratio = AspectRatio::from_window_size(1280., 720.).with_units_per_screen_max(20.);
+    assert_eq!(WorldUnit::scale(&win_info, &aspect_ratio), Vec2::splat(64.));
+    assert_eq!(
+      WorldUnit::right(&aspect_ratio),
+      WorldUnit::new_unchecked(10.)
+    );
+  }
+
+  #[test]
+  fn plugin_applies_units_per_screen_max() {
+    let aspect_ratio = plugin_aspect_ratio(WorldInitPlugin {
+      units_per_screen_max: 16.,
+      spawn_camera: false,
+      ..Default::default()
+    });
+    assert_eq!(aspect_ratio.units_per_screen_max(), 16.);
+    assert_eq!(aspect_ratio.height_over_width(), 720. / 1280.);
+    assert_eq!(
+      WorldUnit::scale(&WinInfo::new(1280., 720.), &aspect_ratio),
+      Vec2::splat(80.)
+    );
+  }
+
+  #[test]
+  fn plugin_defaults_to_default_units_per_screen_max() {
+    let aspect_ratio = plugin_aspect_ratio(WorldInitPlugin {
+      spawn_camera: false,
+      ..Default::default()
+    });
+    assert_eq!(
+      aspect_ratio.units_per_screen_max(),
+      AspectRatio::DEFAULT_UNITS_PER_SCREEN_MAX
+    );
+  }
+
+  fn resize_app() -> App {
+    let mut app = App::new();
+    app
+      .add_event::<WindowResized>()
+      .add_plugins(WorldInitPlugin {
+        spawn_camera: false,
+        handle_exit: false,
+        ..Default::default()
+      });
+    app
+  }
+
+  /// Resizes the window to `width` x `height`, runs a frame, and returns the aspect ratios of the
+  /// `AspectRatioChanged` events sent.
+  fn resize(app: &mut App, width: f32, height: f32) -> Vec<f32> {
+    app.world_mut().send_event(WindowResized {
+      window: Entity::PLACEHOLDER,
+      width,
+      height,
+    });
+    app.update();
+    app
+      .world_mut()
+      .resource_mut::<Events<AspectRatioChanged>>()
+      .drain()
+      .map(|event| event.new)
+      .collect()
+  }
+
+  #[test]
+  fn resize_sends_aspect_ratio_changed_only_on_change() {
+    let mut app = resize_app();
+    assert_eq!(resize(&mut app, 1280., 720.), []);
+    assert_eq!(resize(&mut app, 640., 360.), []);
+    assert_eq!(resize(&mut app, 500., 1000.), [2.]);
+    let win_info = app.world().resource::<WinInfo>();
+    assert_eq!((win_info.width, win_info.height), (500., 1000.));
+  }
+
+  #[test]
+  fn minimize_and_restore_sends_no_aspect_ratio_changed() {
+    let mut app = resize_app();
+    assert_eq!(resize(&mut app, 0., 0.), []);
+    assert_eq!(resize(&mut app, 1280., 0.), []);
+    assert_eq!(resize(&mut app, 1280., 720.), []);
+  }
+
+  fn camera_translation(app: &mut App) -> Vec2 {
+    let mut cameras = app
+      .world_mut()
+      .query_filtered::<&Transform, With<WorldCamera>>();
+    cameras.single(app.world()).translation.truncate()
+  }
+
+  #[test]
+  fn camera_stays_on_world_position_across_resizes() {
+    let pos = WorldVec2::new(WorldUnit::new_unchecked(10.), WorldUnit::new_unchecked(-5.));
+    let mut app = App::new();
+    app
+      .add_event::<WindowResized>()
+      .add_plugins(WorldInitPlugin {
+        initial_camera_position: pos,
+        handle_exit: false,
+        ..Default::default()
+      });
+
+    app.update();
+    assert_eq!(camera_translation(&mut app), Vec2::new(256., -128.));
+
+    resize(&mut app, 640., 360.);
+    assert_eq!(camera_translation(&mut app), Vec2::new(128., -64.));
+  }
+
+  #[test]
+  fn camera_moved_in_update_is_synced_same_frame() {
+    let mut app = App::new();
+    app
+      .add_event::<WindowResized>()
+      .add_plugins(WorldInitPlugin { handle_exit: false, ..Default::default() })
+      .add_systems(Update, |mut camera: Single<&mut WorldCamera>| {
+        camera.pos = WorldVec2::new(WorldUnit::new_unchecked(10.), WorldUnit::new_unchecked(-5.));
+      });
+
+    app.update();
+    assert_eq!(camera_translation(&mut app), Vec2::new(256., -128.));
+  }
+
+  #[test]
+  fn visible_world_follows_camera() {
+    let aspect_ratio = AspectRatio::new(0.5);
+    let camera = WorldCamera {
+      pos: WorldVec2::new(WorldUnit::new_unchecked(5.), WorldUnit::ZERO),
+    };
+    let visible = camera.visible_world(&aspect_ratio);
+    assert_eq!(visible.center(), camera.pos);
+    assert_eq!(
+      visible.half_size(),
+      WorldAabb::screen(&aspect_ratio).half_size()
+    );
+  }
+
+  #[test]
+  fn clicks_use_window_size_resized_in_same_frame() {
+    let mut app = App::new();
+    app
+      .add_event::<WindowResized>()
+      .init_resource::<Time>()
+      .init_resource::<ButtonInput<MouseButton>>()
+      .init_resource::<MouseEventRecorder>()
+      .add_plugins((
+        WorldInitPlugin {
+          screen_width: 200.,
+          screen_height: 200.,
+          units_per_screen_max: 10.,
+          spawn_camera: false,
+          handle_exit: false,
+          ..Default::default()
+        },
+        MousePlugin,
+      ));
+    let mut window = Window::default();
+    window.set_cursor_position(Some(Vec2::new(75., 25.)));
+    app.world_mut().spawn((window, PrimaryWindow));
+    app
+      .world_mut()
+      .resource_mut::<ButtonInput<MouseButton>>()
+      .press(MouseButton::Left);
+
+    resize(&mut app, 100., 100.);
+    let events: Vec<_> = app
+      .world_mut()
+      .resource_mut::<MouseEventRecorder>()
+      .take()
+      .into_iter()
+      .map(|(_, event)| event)
+      .collect();
+    let expected = WorldVec2::new(WorldUnit::new_unchecked(2.5), WorldUnit::new_unchecked(2.5));
+    assert_eq!(
+      events,
+      [MouseEvent::CursorEntered, MouseEvent::LeftClick(expected)]
+    );
+  }
+
+  #[test]
+  fn camera_setup_adds_components_to_spawned_camera() {
+    #[derive(Component)]
+    struct MainCamera;
+
+    let mut app = App::new();
+    app.add_event::<WindowResized>().add_plugins(
+      WorldInitPlugin { handle_exit: false, ..Default::default() }.with_camera_setup(|camera| {
+        camera.insert(MainCamera);
+      }),
+    );
+    app.update();
+
+    let mut cameras = app
+      .world_mut()
+      .query_filtered::<(), (With<Camera2d>, With<WorldCamera>, With<MainCamera>)>();
+    assert_eq!(cameras.iter(app.world()).count(), 1);
   }
 }
@@ -1,22 +1,71 @@
 use std::{
   fmt::{Debug, Display},
-  ops::{Add, AddAssign, Div, Mul, Neg, Sub},
+  hash::{Hash, Hasher},
+  iter::Sum,
+  ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 use bevy::{
-  ecs::system::Resource,
-  math::{Vec2, primitives::Rectangle},
+  ecs::{component::Component, system::Resource},
+  math::{Rot2, Vec2, primitives::Rectangle},
+  transform::components::Transform,
 };
 use ordered_float::NotNan;
 
 use crate::win_info::WinInfo;
 
-#[derive(Resource)]
-pub struct AspectRatio(f32);
+/// The shape and scale of the visible world. The shape is height / width (so a 16:9 landscape
+/// window is `0.5625` and a 9:16 portrait window is `1.777..`). The longer side spans
+/// `units_per_screen_max` world units, and the shorter side proportionally fewer.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct AspectRatio {
+  aspect_ratio: f32,
+  units_per_screen_max: f32,
+}
 
 impl AspectRatio {
+  pub const DEFAULT_UNITS_PER_SCREEN_MAX: f32 = 50.;
+
+  /// Takes the ratio height / width.
   pub fn new(aspect_ratio: f32) -> Self {
-    Self(aspect_ratio)
+    Self {
+      aspect_ratio,
+      units_per_screen_max: Self::DEFAULT_UNITS_PER_SCREEN_MAX,
+    }
+  }
+
+  pub fn from_window_size(width: f32, height: f32) -> Self {
+    Self::new(height / width)
+  }
+
+  /// Sets how many world units span the longer side of the screen.
+  pub fn with_units_per_screen_max(self, units_per_screen_max: f32) -> Self {
+    Self { units_per_screen_max, ..self }
+  }
+
+  pub fn height_over_width(&self) -> f32 {
+    self.aspect_ratio
+  }
+
+  pub fn units_per_screen_max(&self) -> f32 {
+    self.units_per_screen_max
+  }
+}
+
+fn smooth_step(t: f32) -> f32 {
+  let t = t.clamp(0., 1.);
+  t * t * (3. - 2. * t)
+}
+
+/// The conversion factor between world units and the meters used by a physics backend.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct PhysicsScale {
+  pub meters_per_unit: f32,
+}
+
+impl Default for PhysicsScale {
+  fn default() -> Self {
+    Self { meters_per_unit: 1. }
   }
 }
 
@@ -39,6 +88,14 @@ impl Ord for WorldUnit {
   }
 }
 
+/// Hashes consistently with `Eq`: `-0.0` and `+0.0` compare equal, so they hash equal too. Like
+/// `Ord`, this panics on NaN.
+impl Hash for WorldUnit {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    NotNan::new(self.0).unwrap().hash(state);
+  }
+}
+
 impl Display for WorldUnit {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     write!(f, "{}u", self.0)
@@ -52,23 +109,44 @@ impl Debug for WorldUnit {
 }
 
 impl WorldUnit {
-  const UNITS_PER_SCREEN_MAX: f32 = 50.;
-
   pub const ZERO: Self = Self(0.);
   pub const ONE: Self = Self(1.);
 
-  const fn units_per_screen_width(AspectRatio(aspect_ratio): &AspectRatio) -> f32 {
-    Self::UNITS_PER_SCREEN_MAX / aspect_ratio.max(1.)
+  /// Returns `None` if `value` is NaN, which `WorldUnit`'s ordering can't handle.
+  pub const fn new(value: f32) -> Option<Self> {
+    if value.is_nan() {
+      None
+    } else {
+      Some(Self(value))
+    }
+  }
+
+  /// Like `new`, but only checks for NaN in debug builds.
+  pub const fn new_unchecked(value: f32) -> Self {
+    debug_assert!(!value.is_nan());
+    Self(value)
   }
 
-  const fn units_per_screen_height(AspectRatio(aspect_ratio): &AspectRatio) -> f32 {
-    aspect_ratio.min(1.) * Self::UNITS_PER_SCREEN_MAX
+  const fn units_per_screen_width(
+    AspectRatio { aspect_ratio, units_per_screen_max }: &AspectRatio,
+  ) -> f32 {
+    *units_per_screen_max / aspect_ratio.max(1.)
   }
 
+  const fn units_per_screen_height(
+    AspectRatio { aspect_ratio, units_per_screen_max }: &AspectRatio,
+  ) -> f32 {
+    aspect_ratio.min(1.) * *units_per_screen_max
+  }
+
+  /// The width of the visible world: `units_per_screen_max` for landscape aspect ratios, less for
+  /// portrait.
   pub const fn screen_width(aspect_ratio: &AspectRatio) -> Self {
     Self(Self::units_per_screen_width(aspect_ratio))
   }
 
+  /// The height of the visible world: `units_per_screen_max` for portrait aspect ratios, less for
+  /// landscape.
   pub const fn screen_height(aspect_ratio: &AspectRatio) -> Self {
     Self(Self::units_per_screen_height(aspect_ratio))
   }
@@ -83,6 +161,16 @@ impl WorldUnit {
     Self(y * Self::units_per_screen_height(aspect_ratio) / 2.)
   }
 
+  /// The inverse of `normalized_x`. Points off screen map outside of `-1.0..=1.0`.
+  pub const fn to_normalized_x(self, aspect_ratio: &AspectRatio) -> f32 {
+    2. * self.0 / Self::units_per_screen_width(aspect_ratio)
+  }
+
+  /// The inverse of `normalized_y`. Points off screen map outside of `-1.0..=1.0`.
+  pub const fn to_normalized_y(self, aspect_ratio: &AspectRatio) -> f32 {
+    2. * self.0 / Self::units_per_screen_height(aspect_ratio)
+  }
+
   pub const fn top(aspect_ratio: &AspectRatio) -> Self {
     Self::normalized_y(1., aspect_ratio)
   }
@@ -99,9 +187,13 @@ impl WorldUnit {
     Self::normalized_x(1., aspect_ratio)
   }
 
-  const fn scale(win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Vec2 {
-    let window_width = win_info.width.min(win_info.height / aspect_ratio.0);
-    let window_height = window_width * aspect_ratio.0;
+  /// The number of pixels spanned by one world unit along each axis, for drawing at a fixed
+  /// on-screen size regardless of window size.
+  pub const fn scale(win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Vec2 {
+    let window_width = win_info
+      .width
+      .min(win_info.height / aspect_ratio.aspect_ratio);
+    let window_height = window_width * aspect_ratio.aspect_ratio;
     Vec2 {
       x: window_width / Self::units_per_screen_width(aspect_ratio),
       y: window_height / Self::units_per_screen_height(aspect_ratio),
@@ -116,18 +208,33 @@ impl WorldUnit {
     self.0 * Self::scale(win_info, aspect_ratio).y
   }
 
+  /// Divides by the pixels-per-unit scale, or returns `ZERO` if the window is degenerate (e.g. 0x0
+  /// while minimized), so no NaN or infinity escapes.
+  const fn from_scaled(pixels: f32, scale: f32) -> Self {
+    if scale > 0. && scale.is_finite() {
+      Self(pixels / scale)
+    } else {
+      Self::ZERO
+    }
+  }
+
   pub const fn from_x(x: f32, win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Self {
-    Self(x / Self::scale(win_info, aspect_ratio).x)
+    Self::from_scaled(x, Self::scale(win_info, aspect_ratio).x)
   }
 
   pub const fn from_y(y: f32, win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Self {
-    Self(y / Self::scale(win_info, aspect_ratio).y)
+    Self::from_scaled(y, Self::scale(win_info, aspect_ratio).y)
   }
 
   pub const fn to_untyped(self) -> f32 {
     self.0
   }
 
+  /// The inverse of `to_untyped`. Same as `new_unchecked`.
+  pub const fn from_untyped(value: f32) -> Self {
+    Self::new_unchecked(value)
+  }
+
   pub const fn abs(self) -> Self {
     Self(self.0.abs())
   }
@@ -135,6 +242,76 @@ impl WorldUnit {
   pub const fn squared(self) -> f32 {
     self.0 * self.0
   }
+
+  pub const fn is_finite(self) -> bool {
+    self.0.is_finite()
+  }
+
+  pub const fn is_nan(self) -> bool {
+    self.0.is_nan()
+  }
+
+  /// The least non-negative remainder of `self / rhs`, which wraps `self` into `0..rhs`. Returns NaN
+  /// if `rhs` is zero.
+  pub fn rem_euclid(self, rhs: Self) -> Self {
+    Self(self.0.rem_euclid(rhs.0))
+  }
+
+  pub fn fract(self) -> Self {
+    Self(self.0.fract())
+  }
+
+  /// Returns `self` with the sign of `sign`.
+  pub const fn copysign(self, sign: Self) -> Self {
+    Self(self.0.copysign(sign.0))
+  }
+
+  /// Maps infinities and NaN to `ZERO`, so the result is safe to order or hash.
+  pub const fn finite_or_zero(self) -> Self {
+    if self.is_finite() { self } else { Self::ZERO }
+  }
+
+  pub const fn to_meters(self, scale: &PhysicsScale) -> f32 {
+    self.0 * scale.meters_per_unit
+  }
+
+  pub const fn from_meters(meters: f32, scale: &PhysicsScale) -> Self {
+    Self(meters / scale.meters_per_unit)
+  }
+
+  /// Formats like `Display`, but with `suffix` in place of the default `u`.
+  pub fn format_with_suffix(&self, suffix: &str) -> String {
+    format!("{}{suffix}", self.0)
+  }
+
+  pub fn min(self, other: Self) -> Self {
+    Ord::min(self, other)
+  }
+
+  pub fn max(self, other: Self) -> Self {
+    Ord::max(self, other)
+  }
+
+  /// Restricts `self` to `min..=max`. Panics if `min > max`.
+  pub fn clamp(self, min: Self, max: Self) -> Self {
+    Ord::clamp(self, min, max)
+  }
+
+  /// Linearly interpolates between `self` (`t = 0`) and `other` (`t = 1`).
+  pub fn lerp(self, other: Self, t: f32) -> Self {
+    self + (other - self) * t
+  }
+
+  /// Interpolates between `self` and `other`, easing in and out with `3t² - 2t³`. `t` is clamped to
+  /// `0.0..=1.0`.
+  pub fn smooth_step(self, other: Self, t: f32) -> Self {
+    self.lerp(other, smooth_step(t))
+  }
+
+  /// Returns true if `self` and `other` differ by at most `epsilon`.
+  pub fn approx_eq(self, other: Self, epsilon: Self) -> bool {
+    (self - other).abs() <= epsilon
+  }
 }
 
 impl Add for WorldUnit {
@@ -159,6 +336,12 @@ impl Sub for WorldUnit {
   }
 }
 
+impl SubAssign for WorldUnit {
+  fn sub_assign(&mut self, rhs: Self) {
+    self.0 -= rhs.0;
+  }
+}
+
 impl Mul<f32> for WorldUnit {
   type Output = Self;
 
@@ -167,6 +350,12 @@ impl Mul<f32> for WorldUnit {
   }
 }
 
+impl MulAssign<f32> for WorldUnit {
+  fn mul_assign(&mut self, rhs: f32) {
+    self.0 *= rhs;
+  }
+}
+
 impl Mul<WorldUnit> for f32 {
   type Output = WorldUnit;
 
@@ -191,6 +380,12 @@ impl Div<f32> for WorldUnit {
   }
 }
 
+impl DivAssign<f32> for WorldUnit {
+  fn div_assign(&mut self, rhs: f32) {
+    self.0 /= rhs;
+  }
+}
+
 impl Neg for WorldUnit {
   type Output = Self;
 
@@ -199,7 +394,29 @@ impl Neg for WorldUnit {
   }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+impl Sum for WorldUnit {
+  fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+    iter.fold(Self::ZERO, Add::add)
+  }
+}
+
+impl<'a> Sum<&'a WorldUnit> for WorldUnit {
+  fn sum<I: Iterator<Item = &'a WorldUnit>>(iter: I) -> Self {
+    iter.copied().sum()
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+  X,
+  Y,
+}
+
+impl Axis {
+  pub const ALL: [Self; 2] = [Self::X, Self::Y];
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct WorldVec2 {
   pub x: WorldUnit,
   pub y: WorldUnit,
@@ -221,6 +438,32 @@ impl WorldVec2 {
     Self { x, y }
   }
 
+  pub const fn splat(value: WorldUnit) -> Self {
+    Self { x: value, y: value }
+  }
+
+  pub const fn with_x(self, x: WorldUnit) -> Self {
+    Self { x, ..self }
+  }
+
+  pub const fn with_y(self, y: WorldUnit) -> Self {
+    Self { y, ..self }
+  }
+
+  pub const fn get(self, axis: Axis) -> WorldUnit {
+    match axis {
+      Axis::X => self.x,
+      Axis::Y => self.y,
+    }
+  }
+
+  pub const fn set(&mut self, axis: Axis, value: WorldUnit) {
+    match axis {
+      Axis::X => self.x = value,
+      Axis::Y => self.y = value,
+    }
+  }
+
   pub const fn new_normalized(x: f32, y: f32, aspect_ratio: &AspectRatio) -> Self {
     Self {
       x: WorldUnit::normalized_x(x, aspect_ratio),
@@ -228,14 +471,16 @@ impl WorldVec2 {
     }
   }
 
+  /// Converts from pixels with the origin at the top left of the window and y pointing down, as
+  /// returned by `Window::cursor_position`.
   pub const fn from_window_screen_pos(
     pos: Vec2,
     win_info: &WinInfo,
     aspect_ratio: &AspectRatio,
   ) -> Self {
     Self {
-      x: WorldUnit::from_x(pos.x - win_info.width / 2., &win_info, &aspect_ratio),
-      y: WorldUnit::from_y(win_info.height / 2. - pos.y, &win_info, &aspect_ratio),
+      x: WorldUnit::from_x(pos.x - win_info.width / 2., win_info, aspect_ratio),
+      y: WorldUnit::from_y(win_info.height / 2. - pos.y, win_info, aspect_ratio),
     }
   }
 
@@ -250,18 +495,100 @@ impl WorldVec2 {
     }
   }
 
+  pub const fn to_meters(self, scale: &PhysicsScale) -> Vec2 {
+    Vec2 {
+      x: self.x.to_meters(scale),
+      y: self.y.to_meters(scale),
+    }
+  }
+
+  pub const fn from_meters(meters: Vec2, scale: &PhysicsScale) -> Self {
+    Self {
+      x: WorldUnit::from_meters(meters.x, scale),
+      y: WorldUnit::from_meters(meters.y, scale),
+    }
+  }
+
+  pub const fn to_array(self) -> [f32; 2] {
+    [self.x.to_untyped(), self.y.to_untyped()]
+  }
+
+  pub const fn from_array([x, y]: [f32; 2]) -> Self {
+    Self {
+      x: WorldUnit::from_untyped(x),
+      y: WorldUnit::from_untyped(y),
+    }
+  }
+
+  /// Converts to pixels with the origin at the center of the window and y pointing up. Same as
+  /// `to_screen_centered`.
   pub fn to_absolute(self, win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Vec2 {
     Vec2 { x: self.x.0, y: self.y.0 } * WorldUnit::scale(win_info, aspect_ratio)
   }
 
-  /// Returns a Vec2 with x and y ranging between `(-1.)..(1.)`.
+  /// Converts to pixels with the origin at the center of the window and y pointing up, as used by
+  /// `Transform::translation`.
+  pub fn to_screen_centered(self, win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Vec2 {
+    self.to_absolute(win_info, aspect_ratio)
+  }
+
+  /// Converts to pixels with the origin at the top left of the window and y pointing down, as used
+  /// by `Window::cursor_position`. The inverse of `from_window_screen_pos`.
+  pub fn to_window_pixels(self, win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Vec2 {
+    let centered = self.to_screen_centered(win_info, aspect_ratio);
+    Vec2 {
+      x: centered.x + win_info.width / 2.,
+      y: win_info.height / 2. - centered.y,
+    }
+  }
+
+  /// The inverse of `to_absolute`/`to_screen_centered`.
+  pub const fn from_absolute(pos: Vec2, win_info: &WinInfo, aspect_ratio: &AspectRatio) -> Self {
+    Self {
+      x: WorldUnit::from_x(pos.x, win_info, aspect_ratio),
+      y: WorldUnit::from_y(pos.y, win_info, aspect_ratio),
+    }
+  }
+
+  /// Reads the world position of a render `transform`, ignoring its z, rotation, and scale.
+  pub fn from_transform(
+    transform: &Transform,
+    win_info: &WinInfo,
+    aspect_ratio: &AspectRatio,
+  ) -> Self {
+    Self::from_absolute(transform.translation.truncate(), win_info, aspect_ratio)
+  }
+
+  /// Moves a render `transform` to this world position, leaving its z, rotation, and scale as-is.
+  pub fn apply_to_transform(
+    self,
+    transform: &mut Transform,
+    win_info: &WinInfo,
+    aspect_ratio: &AspectRatio,
+  ) {
+    let pos = self.to_absolute(win_info, aspect_ratio);
+    transform.translation.x = pos.x;
+    transform.translation.y = pos.y;
+  }
+
+  /// Same as `to_normalized`: x and y are within `-1.0..=1.0` on screen and outside it off screen.
+  #[deprecated(note = "use to_normalized")]
   pub fn screen_normalized(self, aspect_ratio: &AspectRatio) -> Vec2 {
+    self.to_normalized(aspect_ratio)
+  }
+
+  /// The inverse of `new_normalized`. Points off screen map outside of `-1.0..=1.0`.
+  pub const fn to_normalized(self, aspect_ratio: &AspectRatio) -> Vec2 {
     Vec2 {
-      x: 2. * self.x.to_untyped() / WorldUnit::units_per_screen_width(aspect_ratio),
-      y: 2. * self.y.to_untyped() / WorldUnit::units_per_screen_height(aspect_ratio),
+      x: self.x.to_normalized_x(aspect_ratio),
+      y: self.y.to_normalized_y(aspect_ratio),
     }
   }
 
+  pub const fn is_finite(self) -> bool {
+    self.x.is_finite() && self.y.is_finite()
+  }
+
   pub fn length_squared(self) -> f32 {
     self.x.0 * self.x.0 + self.y.0 * self.y.0
   }
@@ -270,14 +597,123 @@ impl WorldVec2 {
     WorldUnit(self.length_squared().sqrt())
   }
 
-  pub fn normalized(self) -> Self {
-    let length = self.length().0;
+  pub fn distance_squared(self, other: Self) -> f32 {
+    (self - other).length_squared()
+  }
+
+  pub fn distance(self, other: Self) -> WorldUnit {
+    (self - other).length()
+  }
+
+  /// Clamps each component of `self` independently into `bounds`.
+  pub fn clamp_to_aabb(self, bounds: &WorldAabb) -> Self {
     Self {
-      x: WorldUnit(self.x.0 / length),
-      y: WorldUnit(self.y.0 / length),
+      x: self.x.clamp(bounds.min.x, bounds.max.x),
+      y: self.y.clamp(bounds.min.y, bounds.max.y),
     }
   }
 
+  /// Steps from `self` toward `target` by at most `max_delta`, stopping at `target` rather than
+  /// overshooting it.
+  pub fn move_towards(self, target: Self, max_delta: WorldUnit) -> Self {
+    if self.distance(target) <= max_delta {
+      return target;
+    }
+    match (target - self).try_normalize() {
+      Some(direction) => self + max_delta * direction.to_untyped(),
+      None => self,
+    }
+  }
+
+  /// Clamps `self` so that a box of `half_extent` centered on it stays within `bounds`. On an axis
+  /// where the box is larger than `bounds`, centers it in `bounds` instead.
+  pub fn clamp_to_bounds(self, bounds: &WorldAabb, half_extent: Self) -> Self {
+    let inset = WorldAabb::new(bounds.min + half_extent, bounds.max - half_extent);
+    let center = bounds.center();
+    let clamp_axis = |axis| {
+      if inset.min[axis] <= inset.max[axis] {
+        self[axis].clamp(inset.min[axis], inset.max[axis])
+      } else {
+        center[axis]
+      }
+    };
+    Self {
+      x: clamp_axis(Axis::X),
+      y: clamp_axis(Axis::Y),
+    }
+  }
+
+  /// Orders by x, then by y. Like `WorldUnit`'s ordering, this panics on NaN components.
+  pub fn cmp_lexicographic(&self, other: &Self) -> std::cmp::Ordering {
+    self.x.cmp(&other.x).then(self.y.cmp(&other.y))
+  }
+
+  /// Sorts `points` from nearest to farthest from `origin`, keeping the relative order of
+  /// equidistant points. Like `WorldUnit`'s ordering, this panics on NaN components.
+  pub fn sort_by_distance_from(points: &mut [Self], origin: Self) {
+    points.sort_by_key(|point| NotNan::new(origin.distance_squared(*point)).unwrap());
+  }
+
+  /// Wraps each component around `bounds`, so leaving one side re-enters from the opposite side.
+  /// Components on a zero-width axis of `bounds` collapse onto it.
+  pub fn wrap_to_bounds(self, bounds: &WorldAabb) -> Self {
+    let wrap_axis = |axis| {
+      let width = bounds.max[axis] - bounds.min[axis];
+      if width == WorldUnit::ZERO {
+        bounds.min[axis]
+      } else {
+        bounds.min[axis] + (self[axis] - bounds.min[axis]).rem_euclid(width)
+      }
+    };
+    Self {
+      x: wrap_axis(Axis::X),
+      y: wrap_axis(Axis::Y),
+    }
+  }
+
+  /// The mean of `points`, or `None` if there are no points. For very many points, `WorldVec2Mean`
+  /// accumulates with less rounding error.
+  pub fn centroid(points: impl IntoIterator<Item = Self>) -> Option<Self> {
+    let (sum, count) = points
+      .into_iter()
+      .fold((Self::ZERO, 0u32), |(sum, count), point| {
+        (sum + point, count + 1)
+      });
+    (count != 0).then(|| sum / count as f32)
+  }
+
+  /// Returns `self` if it lies within `bounds`, or an error describing the first axis on which it
+  /// falls outside.
+  pub fn validate_in(self, bounds: &WorldAabb) -> Result<Self, OutOfBoundsError> {
+    for axis in Axis::ALL {
+      let distance = if self[axis] < bounds.min[axis] {
+        bounds.min[axis] - self[axis]
+      } else if self[axis] > bounds.max[axis] {
+        self[axis] - bounds.max[axis]
+      } else {
+        continue;
+      };
+      return Err(OutOfBoundsError { point: self, axis, distance });
+    }
+    Ok(self)
+  }
+
+  /// Returns the candidate nearest to `self`, or `None` if there are no candidates. Ties resolve to
+  /// the earliest candidate.
+  pub fn closest_of(self, candidates: impl IntoIterator<Item = Self>) -> Option<Self> {
+    candidates.into_iter().min_by(|a, b| {
+      self
+        .distance_squared(*a)
+        .total_cmp(&self.distance_squared(*b))
+    })
+  }
+
+  /// Returns `self` scaled to unit length, or `ZERO` if `self` has no direction (zero or
+  /// non-finite length). Use `try_normalize` to distinguish that case.
+  pub fn normalized(self) -> Self {
+    self.try_normalize().unwrap_or(Self::ZERO)
+  }
+
   pub fn try_normalize(self) -> Option<Self> {
     self.to_untyped().try_normalize().map(Self::from_untyped)
   }
@@ -285,6 +721,158 @@ impl WorldVec2 {
   pub fn dot(self, other: Self) -> f32 {
     self.x.0 * other.x.0 + self.y.0 * other.y.0
   }
+
+  pub fn abs(self) -> Self {
+    Self { x: self.x.abs(), y: self.y.abs() }
+  }
+
+  /// Returns the sign of each component as `1.` or `-1.`, following `f32::signum` (so `-0.` maps
+  /// to `-1.`).
+  pub fn signum(self) -> Vec2 {
+    self.to_untyped().signum()
+  }
+
+  pub fn min_element(self) -> WorldUnit {
+    self.x.min(self.y)
+  }
+
+  pub fn max_element(self) -> WorldUnit {
+    self.x.max(self.y)
+  }
+
+  /// Returns `self` rotated 90 degrees counterclockwise, i.e. `(-y, x)`.
+  pub fn perp(self) -> Self {
+    Self { x: -self.y, y: self.x }
+  }
+
+  /// The 2D cross product, which is positive if `other` is counterclockwise from `self`.
+  pub fn perp_dot(self, other: Self) -> f32 {
+    self.x.0 * other.y.0 - self.y.0 * other.x.0
+  }
+
+  fn to_grid(self, spacing: WorldUnit, round: impl Fn(f32) -> f32) -> Self {
+    if spacing == WorldUnit::ZERO {
+      return self;
+    }
+    Self {
+      x: WorldUnit(round(self.x.0 / spacing.0) * spacing.0),
+      y: WorldUnit(round(self.y.0 / spacing.0) * spacing.0),
+    }
+  }
+
+  /// Rounds each component to the nearest multiple of `spacing`. Returns `self` unchanged if
+  /// `spacing` is zero.
+  pub fn snap_to_grid(self, spacing: WorldUnit) -> Self {
+    self.to_grid(spacing, f32::round)
+  }
+
+  /// Rounds each component down to a multiple of `spacing`. Returns `self` unchanged if `spacing`
+  /// is zero.
+  pub fn floor_to_grid(self, spacing: WorldUnit) -> Self {
+    self.to_grid(spacing, f32::floor)
+  }
+
+  /// Rounds each component up to a multiple of `spacing`. Returns `self` unchanged if `spacing` is
+  /// zero.
+  pub fn ceil_to_grid(self, spacing: WorldUnit) -> Self {
+    self.to_grid(spacing, f32::ceil)
+  }
+
+  /// The signed angle in radians from `self` to `other`, positive if counterclockwise.
+  pub fn angle_between(self, other: Self) -> f32 {
+    self.perp_dot(other).atan2(self.dot(other))
+  }
+
+  /// Rotates `self` counterclockwise by `radians` around `pivot`.
+  pub fn rotate_around(self, pivot: Self, radians: f32) -> Self {
+    pivot + Self::from_untyped(Vec2::from_angle(radians).rotate((self - pivot).to_untyped()))
+  }
+
+  /// Scales `self` down to at most `max` long, preserving its direction.
+  pub fn clamp_length_max(self, max: WorldUnit) -> Self {
+    self.clamp_length(WorldUnit::ZERO, max)
+  }
+
+  /// Scales `self` to a length within `min..=max`, preserving its direction. The zero vector has no
+  /// direction, so it is returned unchanged.
+  ///
+  /// Panics if `min > max`. Like `WorldUnit`'s ordering, this also panics on NaN.
+  pub fn clamp_length(self, min: WorldUnit, max: WorldUnit) -> Self {
+    debug_assert!(min <= max);
+    let Some(direction) = self.try_normalize() else {
+      return self;
+    };
+    let length = self.length();
+    let clamped = length.clamp(min, max);
+    if clamped == length {
+      self
+    } else {
+      clamped * direction.to_untyped()
+    }
+  }
+
+  /// Projects `self` onto `other`. Returns `ZERO` if `other` is the zero vector.
+  pub fn project_onto(self, other: Self) -> Self {
+    let other_length_squared = other.length_squared();
+    if other_length_squared == 0. {
+      return Self::ZERO;
+    }
+    other * (self.dot(other) / other_length_squared)
+  }
+
+  /// Returns the component of `self` perpendicular to `other`. Returns `self` if `other` is the
+  /// zero vector.
+  pub fn reject_from(self, other: Self) -> Self {
+    self - self.project_onto(other)
+  }
+
+  /// Reflects `self` across the surface with the given `normal`, which must be unit length.
+  pub fn reflect(self, normal: Self) -> Self {
+    self - normal * (2. * self.dot(normal))
+  }
+
+  /// Divides each component by `rhs`, or returns `None` if `rhs` is zero.
+  pub fn checked_div(self, rhs: f32) -> Option<Self> {
+    (rhs != 0.).then(|| self / rhs)
+  }
+
+  /// Multiplies `self` and `other` component-wise. The result is in square world units, so it is
+  /// only meaningful when one side is really a per-axis factor expressed as a `WorldVec2`. To scale
+  /// a unitless direction by a length, use `WorldUnit * Vec2`.
+  pub fn component_mul(self, other: Self) -> Self {
+    Self {
+      x: WorldUnit(self.x.0 * other.x.0),
+      y: WorldUnit(self.y.0 * other.y.0),
+    }
+  }
+
+  /// Returns true if each component of `self` and `other` differ by at most `epsilon`.
+  pub fn approx_eq(self, other: Self, epsilon: WorldUnit) -> bool {
+    self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon)
+  }
+
+  /// Linearly interpolates between `self` (`t = 0`) and `other` (`t = 1`).
+  pub fn lerp(self, other: Self, t: f32) -> Self {
+    self + (other - self) * t
+  }
+
+  /// Interpolates between `self` and `other`, easing in and out with `3t² - 2t³`. `t` is clamped to
+  /// `0.0..=1.0`.
+  pub fn smooth_step(self, other: Self, t: f32) -> Self {
+    self.lerp(other, smooth_step(t))
+  }
+
+  /// Exponentially eases `self` toward `target`, framerate-independently. Higher `smoothness`
+  /// values converge faster.
+  pub fn smooth_damp(self, target: Self, smoothness: f32, dt: f32) -> Self {
+    self.lerp(target, 1. - (-smoothness * dt).exp())
+  }
+
+  /// Like `smooth_damp`, but never moves more than `max_speed * dt` in a single step.
+  pub fn damp_capped(self, target: Self, smoothness: f32, max_speed: WorldUnit, dt: f32) -> Self {
+    let step = self.smooth_damp(target, smoothness, dt) - self;
+    self + step.clamp_length_max(max_speed * dt)
+  }
 }
 
 impl Add for WorldVec2 {
@@ -310,6 +898,13 @@ impl Sub for WorldVec2 {
   }
 }
 
+impl SubAssign for WorldVec2 {
+  fn sub_assign(&mut self, rhs: Self) {
+    self.x -= rhs.x;
+    self.y -= rhs.y;
+  }
+}
+
 impl Mul<f32> for WorldVec2 {
   type Output = Self;
 
@@ -318,6 +913,13 @@ impl Mul<f32> for WorldVec2 {
   }
 }
 
+impl MulAssign<f32> for WorldVec2 {
+  fn mul_assign(&mut self, rhs: f32) {
+    self.x *= rhs;
+    self.y *= rhs;
+  }
+}
+
 impl Mul<WorldVec2> for f32 {
   type Output = WorldVec2;
 
@@ -326,6 +928,23 @@ impl Mul<WorldVec2> for f32 {
   }
 }
 
+/// Dividing by zero gives infinite (or NaN, for zero components) components; use `checked_div` to
+/// avoid that.
+impl Div<f32> for WorldVec2 {
+  type Output = Self;
+
+  fn div(self, rhs: f32) -> Self::Output {
+    Self { x: self.x / rhs, y: self.y / rhs }
+  }
+}
+
+impl DivAssign<f32> for WorldVec2 {
+  fn div_assign(&mut self, rhs: f32) {
+    self.x /= rhs;
+    self.y /= rhs;
+  }
+}
+
 impl Neg for WorldVec2 {
   type Output = Self;
 
@@ -334,14 +953,1010 @@ impl Neg for WorldVec2 {
   }
 }
 
+/// Reinterprets the components of a `Vec2` as world units. This is not a coordinate conversion; use
+/// `from_window_screen_pos` to convert from pixels.
+impl From<Vec2> for WorldVec2 {
+  fn from(vec: Vec2) -> Self {
+    Self::from_untyped(vec)
+  }
+}
+
+/// Reinterprets the components of a `WorldVec2` as raw floats. This is not a coordinate conversion;
+/// use `to_absolute` to convert to pixels.
+impl From<WorldVec2> for Vec2 {
+  fn from(vec: WorldVec2) -> Self {
+    vec.to_untyped()
+  }
+}
+
+impl Index<Axis> for WorldVec2 {
+  type Output = WorldUnit;
+
+  fn index(&self, axis: Axis) -> &WorldUnit {
+    match axis {
+      Axis::X => &self.x,
+      Axis::Y => &self.y,
+    }
+  }
+}
+
+impl IndexMut<Axis> for WorldVec2 {
+  fn index_mut(&mut self, axis: Axis) -> &mut WorldUnit {
+    match axis {
+      Axis::X => &mut self.x,
+      Axis::Y => &mut self.y,
+    }
+  }
+}
+
+impl Sum for WorldVec2 {
+  fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+    iter.fold(Self::ZERO, Add::add)
+  }
+}
+
+impl<'a> Sum<&'a WorldVec2> for WorldVec2 {
+  fn sum<I: Iterator<Item = &'a WorldVec2>>(iter: I) -> Self {
+    iter.copied().sum()
+  }
+}
+
+/// Incrementally computes the mean of a stream of points. Each push nudges the running mean toward
+/// the new point, which avoids the precision loss of summing many points before dividing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WorldVec2Mean {
+  mean: WorldVec2,
+  count: u32,
+}
+
+impl WorldVec2Mean {
+  pub fn push(&mut self, point: WorldVec2) {
+    self.count += 1;
+    self.mean += (point - self.mean) * (1. / self.count as f32);
+  }
+
+  pub fn mean(&self) -> Option<WorldVec2> {
+    (self.count != 0).then_some(self.mean)
+  }
+
+  pub fn count(&self) -> u32 {
+    self.count
+  }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
 pub struct WorldRect(Rectangle);
 
 impl WorldRect {
+  /// Panics in debug builds if either dimension is negative or non-finite; use `try_new` to check.
   pub fn new(width: WorldUnit, height: WorldUnit) -> Self {
+    debug_assert!(Self::is_valid_size(width, height));
     Self(Rectangle::new(width.to_untyped(), height.to_untyped()))
   }
 
+  /// Returns `None` if either dimension is negative or non-finite.
+  pub fn try_new(width: WorldUnit, height: WorldUnit) -> Option<Self> {
+    Self::is_valid_size(width, height).then(|| Self::new(width, height))
+  }
+
+  fn is_valid_size(width: WorldUnit, height: WorldUnit) -> bool {
+    [width, height]
+      .into_iter()
+      .all(|dim| dim.is_finite() && dim.to_untyped() >= 0.)
+  }
+
+  /// Returns the point in this rect closest to `point`, treating the rect as centered at the world
+  /// origin. Use `at` to get a rect placed elsewhere.
   pub fn closest_point(&self, point: WorldVec2) -> WorldVec2 {
     WorldVec2::from_untyped(self.0.closest_point(point.to_untyped()))
   }
+
+  /// Returns true if `point` lies within this rect, treating the rect as centered at the world
+  /// origin. Use `at` to get a rect placed elsewhere.
+  pub fn contains(&self, point: WorldVec2) -> bool {
+    self.at(WorldVec2::ZERO).contains(point)
+  }
+
+  /// Places this rect with its center at `center`.
+  pub fn at(&self, center: WorldVec2) -> WorldAabb {
+    WorldAabb::from_center_half_size(center, self.half_size())
+  }
+
+  pub fn half_size(&self) -> WorldVec2 {
+    WorldVec2::from_untyped(self.0.half_size)
+  }
+
+  /// Returns true if this rect, centered at `center`, overlaps `other`, centered at
+  /// `other_center`. Rects that only touch along an edge are considered overlapping.
+  pub fn intersects(&self, center: WorldVec2, other: &WorldRect, other_center: WorldVec2) -> bool {
+    let half_size = self.half_size();
+    let other_half_size = other.half_size();
+    (center.x - other_center.x).abs() <= half_size.x + other_half_size.x
+      && (center.y - other_center.y).abs() <= half_size.y + other_half_size.y
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutOfBoundsError {
+  pub point: WorldVec2,
+  pub axis: Axis,
+  /// How far outside the bounds `point` lies along `axis`.
+  pub distance: WorldUnit,
+}
+
+impl Display for OutOfBoundsError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let axis = match self.axis {
+      Axis::X => "x",
+      Axis::Y => "y",
+    };
+    write!(
+      f,
+      "{} is out of bounds by {} along the {axis} axis",
+      self.point, self.distance
+    )
+  }
+}
+
+impl std::error::Error for OutOfBoundsError {}
+
+/// A rect positioned anywhere in world space and rotated counterclockwise by `rotation` radians
+/// about its center.
+#[derive(Clone, Copy, Debug)]
+pub struct WorldOrientedRect {
+  pub center: WorldVec2,
+  pub half_size: WorldVec2,
+  pub rotation: f32,
+}
+
+impl WorldOrientedRect {
+  fn local_rect(&self) -> WorldRect {
+    WorldRect::new(self.half_size.x * 2., self.half_size.y * 2.)
+  }
+
+  fn world_to_local(&self, point: WorldVec2) -> WorldVec2 {
+    WorldVec2::from_untyped(Rot2::radians(-self.rotation) * (point - self.center).to_untyped())
+  }
+
+  fn local_to_world(&self, point: WorldVec2) -> WorldVec2 {
+    self.center + WorldVec2::from_untyped(Rot2::radians(self.rotation) * point.to_untyped())
+  }
+
+  pub fn contains(&self, point: WorldVec2) -> bool {
+    self.local_rect().contains(self.world_to_local(point))
+  }
+
+  pub fn closest_point(&self, point: WorldVec2) -> WorldVec2 {
+    self.local_to_world(self.local_rect().closest_point(self.world_to_local(point)))
+  }
+}
+
+/// An axis-aligned box positioned anywhere in world space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorldAabb {
+  pub min: WorldVec2,
+  pub max: WorldVec2,
+}
+
+impl WorldAabb {
+  pub const fn new(min: WorldVec2, max: WorldVec2) -> Self {
+    Self { min, max }
+  }
+
+  pub fn from_center_half_size(center: WorldVec2, half_size: WorldVec2) -> Self {
+    Self {
+      min: center - half_size,
+      max: center + half_size,
+    }
+  }
+
+  /// The smallest box containing every point, or `None` if there are no points.
+  pub fn from_points(points: impl IntoIterator<Item = WorldVec2>) -> Option<Self> {
+    let mut points = points.into_iter();
+    let first = points.next()?;
+    Some(points.fold(Self::new(first, first), |aabb, point| Self {
+      min: WorldVec2::new(aabb.min.x.min(point.x), aabb.min.y.min(point.y)),
+      max: WorldVec2::new(aabb.max.x.max(point.x), aabb.max.y.max(point.y)),
+    }))
+  }
+
+  /// The box centered at `center` that spans `pixel_size` on screen, e.g. for laying out text
+  /// measured in pixels.
+  pub fn from_pixel_size(
+    center: WorldVec2,
+    pixel_size: Vec2,
+    win_info: &WinInfo,
+    aspect_ratio: &AspectRatio,
+  ) -> Self {
+    let half_size = WorldVec2 {
+      x: WorldUnit::from_x(pixel_size.x / 2., win_info, aspect_ratio),
+      y: WorldUnit::from_y(pixel_size.y / 2., win_info, aspect_ratio),
+    };
+    Self::from_center_half_size(center, half_size)
+  }
+
+  /// The region of the world visible on screen.
+  pub const fn screen(aspect_ratio: &AspectRatio) -> Self {
+    Self {
+      min: WorldVec2::new(
+        WorldUnit::left(aspect_ratio),
+        WorldUnit::bottom(aspect_ratio),
+      ),
+      max: WorldVec2::new(WorldUnit::right(aspect_ratio), WorldUnit::top(aspect_ratio)),
+    }
+  }
+
+  pub fn center(&self) -> WorldVec2 {
+    (self.min + self.max) * 0.5
+  }
+
+  pub fn half_size(&self) -> WorldVec2 {
+    (self.max - self.min) * 0.5
+  }
+
+  /// Returns true if `point` lies within this box, including its edges.
+  pub fn contains(&self, point: WorldVec2) -> bool {
+    (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+  }
+
+  /// Returns the point in this box closest to `point`, which is `point` itself if it is inside.
+  pub fn closest_point(&self, point: WorldVec2) -> WorldVec2 {
+    point.clamp_to_aabb(self)
+  }
+
+  /// Casts a ray from `origin` in direction `dir` and returns the distance to where it first enters
+  /// this box, which is `ZERO` if `origin` is already inside. Returns `None` if the ray misses or
+  /// `dir` is the zero vector.
+  pub fn ray_intersection(&self, origin: WorldVec2, dir: WorldVec2) -> Option<WorldUnit> {
+    let dir = dir.try_normalize()?;
+    let mut t_enter = 0f32;
+    let mut t_exit = f32::INFINITY;
+    for axis in Axis::ALL {
+      let origin = origin[axis].0;
+      let dir = dir[axis].0;
+      let (min, max) = (self.min[axis].0, self.max[axis].0);
+      if dir == 0. {
+        // Parallel to this slab, so the ray is either always or never between its planes.
+        if origin < min || origin > max {
+          return None;
+        }
+        continue;
+      }
+
+      let t_min = (min - origin) / dir;
+      let t_max = (max - origin) / dir;
+      t_enter = t_enter.max(t_min.min(t_max));
+      t_exit = t_exit.min(t_min.max(t_max));
+      if t_enter > t_exit {
+        return None;
+      }
+    }
+    Some(WorldUnit(t_enter))
+  }
+
+  /// If `point` is inside this box, returns its distance to the nearest edge, i.e. how far it
+  /// would need to move to exit the box.
+  pub fn penetration_depth(&self, point: WorldVec2) -> Option<WorldUnit> {
+    self.contains(point).then(|| {
+      (point.x - self.min.x)
+        .min(self.max.x - point.x)
+        .min(point.y - self.min.y)
+        .min(self.max.y - point.y)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn unit(value: f32) -> WorldUnit {
+    WorldUnit(value)
+  }
+
+  fn vec2(x: f32, y: f32) -> WorldVec2 {
+    WorldVec2::new(unit(x), unit(y))
+  }
+
+  #[test]
+  fn world_unit_assign_ops() {
+    let mut value = unit(6.);
+    value -= unit(2.);
+    assert_eq!(value, unit(4.));
+    value *= 3.;
+    assert_eq!(value, unit(12.));
+    value /= 4.;
+    assert_eq!(value, unit(3.));
+    value += unit(1.);
+    assert_eq!(value, unit(4.));
+  }
+
+  #[test]
+  fn world_vec2_assign_ops() {
+    let mut value = vec2(6., -2.);
+    value -= vec2(2., 1.);
+    assert_eq!((value.x, value.y), (unit(4.), unit(-3.)));
+    value *= 2.;
+    assert_eq!((value.x, value.y), (unit(8.), unit(-6.)));
+    value /= 4.;
+    assert_eq!((value.x, value.y), (unit(2.), unit(-1.5)));
+    value += vec2(1., 1.);
+    assert_eq!((value.x, value.y), (unit(3.), unit(-0.5)));
+    assert_eq!(((-value).x, (-value).y), (unit(-3.), unit(0.5)));
+  }
+
+  #[test]
+  fn damp_capped_limits_step_to_max_speed() {
+    let start = WorldVec2::ZERO;
+    let target = vec2(100., 0.);
+    let step = start.damp_capped(target, 10., unit(2.), 0.1);
+    assert!((step.x.0 - 0.2).abs() < 1e-6);
+    assert_eq!(step.y, WorldUnit::ZERO);
+    assert!(start.smooth_damp(target, 10., 0.1).x > unit(60.));
+
+    let near = vec2(99.99, 0.);
+    let capped = near.damp_capped(target, 10., unit(2.), 0.1);
+    let damped = near.smooth_damp(target, 10., 0.1);
+    assert_eq!((capped.x, capped.y), (damped.x, damped.y));
+  }
+
+  #[test]
+  fn world_vec2_works_as_map_key() {
+    let mut map = std::collections::HashMap::new();
+    map.insert(vec2(1., 2.), "a");
+    map.insert(vec2(2., 1.), "b");
+    assert_eq!(map.get(&vec2(1., 2.)), Some(&"a"));
+    assert_eq!(map.get(&vec2(2., 1.)), Some(&"b"));
+    assert_eq!(map.get(&vec2(1., 1.)), None);
+  }
+
+  #[test]
+  fn signed_zeros_hash_equal() {
+    use std::hash::BuildHasher;
+
+    let hasher = std::collections::hash_map::RandomState::new();
+    assert_eq!(unit(0.), unit(-0.));
+    assert_eq!(hasher.hash_one(unit(0.)), hasher.hash_one(unit(-0.)));
+    assert_eq!(
+      hasher.hash_one(vec2(0., -0.)),
+      hasher.hash_one(vec2(-0., 0.))
+    );
+  }
+
+  #[test]
+  fn approx_eq_compares_within_epsilon() {
+    let epsilon = unit(0.01);
+    assert!(unit(1.).approx_eq(unit(1.005), epsilon));
+    assert!(unit(1.).approx_eq(unit(1.01), epsilon));
+    assert!(!unit(1.).approx_eq(unit(1.02), epsilon));
+    assert!(!unit(1.).approx_eq(unit(0.98), epsilon));
+
+    assert!(vec2(1., 2.).approx_eq(vec2(1.005, 1.995), epsilon));
+    assert!(!vec2(1., 2.).approx_eq(vec2(1.005, 2.02), epsilon));
+    assert!(!vec2(1., 2.).approx_eq(vec2(0.98, 2.), epsilon));
+  }
+
+  #[test]
+  fn reflect_bounces_off_surface() {
+    assert_eq!(vec2(1., -2.).reflect(WorldVec2::Y), vec2(1., 2.));
+    assert_eq!(vec2(0., -1.).reflect(WorldVec2::Y), vec2(0., 1.));
+  }
+
+  #[test]
+  fn project_and_reject_split_a_vector() {
+    let v = vec2(3., 4.);
+    let onto = vec2(2., 0.);
+    assert_eq!(v.project_onto(onto), vec2(3., 0.));
+    assert_eq!(v.reject_from(onto), vec2(0., 4.));
+    assert_eq!(v.project_onto(WorldVec2::ZERO), WorldVec2::ZERO);
+    assert_eq!(v.reject_from(WorldVec2::ZERO), v);
+  }
+
+  #[test]
+  fn perp_rotates_counterclockwise() {
+    assert_eq!(WorldVec2::X.perp(), WorldVec2::Y);
+    assert_eq!(WorldVec2::Y.perp(), -WorldVec2::X);
+    assert!(WorldVec2::X.perp_dot(WorldVec2::Y) > 0.);
+    assert!(WorldVec2::Y.perp_dot(WorldVec2::X) < 0.);
+    assert_eq!(vec2(2., 3.).perp_dot(vec2(4., 6.)), 0.);
+  }
+
+  #[test]
+  fn closest_of_picks_nearest_candidate() {
+    let origin = vec2(1., 1.);
+    assert_eq!(
+      origin.closest_of([vec2(5., 5.), vec2(2., 1.5), vec2(-3., 0.)]),
+      Some(vec2(2., 1.5))
+    );
+    assert_eq!(
+      origin.closest_of([vec2(3., 1.), vec2(1., 3.), vec2(-1., 1.)]),
+      Some(vec2(3., 1.))
+    );
+    assert_eq!(origin.closest_of([]), None);
+  }
+
+  #[test]
+  fn clamp_restricts_to_range() {
+    assert_eq!(unit(0.5).clamp(unit(0.), unit(1.)), unit(0.5));
+    assert_eq!(unit(-2.).clamp(unit(0.), unit(1.)), unit(0.));
+    assert_eq!(unit(2.).clamp(unit(0.), unit(1.)), unit(1.));
+    assert_eq!(unit(1.).clamp(unit(0.), unit(1.)), unit(1.));
+  }
+
+  #[test]
+  fn clamp_to_aabb_handles_inside_outside_and_boundary() {
+    let bounds = WorldAabb::new(vec2(-1., -2.), vec2(3., 4.));
+    assert_eq!(vec2(0., 0.).clamp_to_aabb(&bounds), vec2(0., 0.));
+    assert_eq!(vec2(-5., 10.).clamp_to_aabb(&bounds), vec2(-1., 4.));
+    assert_eq!(vec2(5., -10.).clamp_to_aabb(&bounds), vec2(3., -2.));
+    for boundary in [vec2(-1., 0.), vec2(3., 0.), vec2(0., -2.), vec2(0., 4.)] {
+      assert_eq!(boundary.clamp_to_aabb(&bounds), boundary);
+    }
+  }
+
+  #[test]
+  fn format_with_suffix_replaces_unit_suffix() {
+    assert_eq!(unit(2.5).to_string(), "2.5u");
+    assert_eq!(unit(2.5).format_with_suffix(" tiles"), "2.5 tiles");
+    assert_eq!(unit(-1.).format_with_suffix("m"), "-1m");
+  }
+
+  #[test]
+  fn clamp_length_scales_only_when_out_of_range() {
+    let within = vec2(3., 4.);
+    assert_eq!(within.clamp_length(unit(1.), unit(10.)), within);
+    assert_eq!(within.clamp_length_max(unit(5.)), within);
+    assert!(
+      within
+        .clamp_length_max(unit(2.5))
+        .approx_eq(vec2(1.5, 2.), unit(1e-6))
+    );
+    assert!(
+      within
+        .clamp_length(unit(10.), unit(20.))
+        .approx_eq(vec2(6., 8.), unit(1e-6))
+    );
+    assert_eq!(
+      WorldVec2::ZERO.clamp_length(unit(1.), unit(2.)),
+      WorldVec2::ZERO
+    );
+    assert_eq!(WorldVec2::ZERO.clamp_length_max(unit(1.)), WorldVec2::ZERO);
+  }
+
+  #[test]
+  #[should_panic]
+  fn clamp_length_rejects_inverted_range() {
+    WorldVec2::ZERO.clamp_length(unit(2.), unit(1.));
+  }
+
+  #[test]
+  fn from_pixel_size_converts_to_world_box() {
+    let win_info = WinInfo::new(500., 500.);
+    let aspect_ratio = AspectRatio::new(1.);
+    let aabb =
+      WorldAabb::from_pixel_size(vec2(1., 1.), Vec2::new(40., 20.), &win_info, &aspect_ratio);
+    assert_eq!(aabb, WorldAabb::new(vec2(-1., 0.), vec2(3., 2.)));
+  }
+
+  #[test]
+  fn component_helpers_handle_signs() {
+    let v = vec2(-3., 2.);
+    assert_eq!(v.abs(), vec2(3., 2.));
+    assert_eq!(v.signum(), Vec2::new(-1., 1.));
+    assert_eq!(v.min_element(), unit(-3.));
+    assert_eq!(v.max_element(), unit(2.));
+
+    let negative = vec2(-1., -4.);
+    assert_eq!(negative.abs(), vec2(1., 4.));
+    assert_eq!(negative.min_element(), unit(-4.));
+    assert_eq!(negative.max_element(), unit(-1.));
+
+    let zeros = vec2(-0., 0.);
+    assert_eq!(zeros.signum(), Vec2::new(-1., 1.));
+    assert!(zeros.abs().x.to_untyped().is_sign_positive());
+    assert_eq!(zeros.min_element(), WorldUnit::ZERO);
+    assert_eq!(zeros.max_element(), WorldUnit::ZERO);
+  }
+
+  #[test]
+  fn running_mean_matches_exact_mean() {
+    let points: Vec<_> = (0..10_000)
+      .map(|i| vec2((i % 97) as f32 * 0.37 - 10., (i % 13) as f32 * 1.9 + 3.))
+      .collect();
+    let mut mean = WorldVec2Mean::default();
+    assert_eq!(mean.mean(), None);
+    for point in &points {
+      mean.push(*point);
+    }
+    assert_eq!(mean.count(), 10_000);
+    let (sum_x, sum_y) = points.iter().fold((0f64, 0f64), |(x, y), point| {
+      (x + point.x.0 as f64, y + point.y.0 as f64)
+    });
+    let exact = vec2((sum_x / 10_000.) as f32, (sum_y / 10_000.) as f32);
+    assert!(mean.mean().unwrap().approx_eq(exact, unit(1e-3)));
+  }
+
+  #[test]
+  fn sums_world_values() {
+    let points = vec![vec2(1., 2.), vec2(-3., 0.5), vec2(4., -1.)];
+    assert_eq!(points.iter().sum::<WorldVec2>(), vec2(2., 1.5));
+    assert_eq!(points.into_iter().sum::<WorldVec2>(), vec2(2., 1.5));
+    assert_eq!([unit(1.), unit(2.5)].iter().sum::<WorldUnit>(), unit(3.5));
+    assert_eq!(
+      Vec::<WorldVec2>::new().into_iter().sum::<WorldVec2>(),
+      WorldVec2::ZERO
+    );
+  }
+
+  #[test]
+  fn positioned_rect_closest_point_and_contains() {
+    let rect = WorldRect::new(unit(4.), unit(2.));
+    let placed = rect.at(vec2(10., -5.));
+    assert!(placed.contains(vec2(11., -4.5)));
+    assert!(placed.contains(vec2(12., -4.)));
+    assert!(!placed.contains(vec2(0., 0.)));
+    assert!(!rect.contains(vec2(11., -4.5)));
+    assert_eq!(placed.closest_point(vec2(20., -5.)), vec2(12., -5.));
+    assert_eq!(placed.closest_point(vec2(0., 0.)), vec2(8., -4.));
+    assert_eq!(placed.closest_point(vec2(9., -5.5)), vec2(9., -5.5));
+    assert_eq!(rect.closest_point(vec2(20., -5.)), vec2(2., -1.));
+  }
+
+  #[test]
+  fn new_rejects_nan() {
+    assert_eq!(WorldUnit::new(f32::NAN), None);
+    assert_eq!(WorldUnit::new(1.5), Some(unit(1.5)));
+    assert_eq!(WorldUnit::new(f32::INFINITY), Some(unit(f32::INFINITY)));
+
+    let (a, b) = (WorldUnit::new(-1.).unwrap(), WorldUnit::new(2.).unwrap());
+    assert!(a < b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Less);
+    assert_eq!(a.max(b), b);
+  }
+
+  #[test]
+  fn scale_fits_world_into_letterboxed_window() {
+    let aspect_ratio = AspectRatio::new(1.);
+    assert_eq!(
+      WorldUnit::scale(&WinInfo::new(1000., 500.), &aspect_ratio),
+      Vec2::splat(10.)
+    );
+    assert_eq!(
+      WorldUnit::scale(&WinInfo::new(500., 1500.), &aspect_ratio),
+      Vec2::splat(10.)
+    );
+    assert_eq!(
+      unit(2.).to_x(&WinInfo::new(1000., 500.), &aspect_ratio),
+      20.
+    );
+  }
+
+  #[test]
+  fn vec2_conversions_round_trip() {
+    let vec = Vec2::new(1.25, -7.5);
+    let world = WorldVec2::from(vec);
+    assert_eq!(world, vec2(1.25, -7.5));
+    assert_eq!(Vec2::from(world), vec);
+    let world: WorldVec2 = Vec2::new(f32::MIN_POSITIVE, f32::MAX).into();
+    assert_eq!(Vec2::from(world), Vec2::new(f32::MIN_POSITIVE, f32::MAX));
+  }
+
+  #[test]
+  fn axis_indexing_reconstructs_vector() {
+    let original = vec2(3., -4.);
+    let mut rebuilt = WorldVec2::ZERO;
+    for axis in Axis::ALL {
+      rebuilt[axis] = original[axis];
+    }
+    assert_eq!(rebuilt, original);
+
+    let mut set = WorldVec2::ZERO;
+    for axis in Axis::ALL {
+      set.set(axis, original.get(axis));
+    }
+    assert_eq!(set, original);
+    assert_eq!(original[Axis::X], unit(3.));
+    assert_eq!(original[Axis::Y], unit(-4.));
+  }
+
+  #[test]
+  fn normalized_round_trips() {
+    for aspect_ratio in [AspectRatio::new(0.5625), AspectRatio::new(16. / 9.)] {
+      for (x, y) in [(0., 0.), (1., -1.), (-0.5, 0.25)] {
+        let world = WorldVec2::new_normalized(x, y, &aspect_ratio);
+        assert_eq!(world.to_normalized(&aspect_ratio), Vec2::new(x, y));
+      }
+      assert_eq!(
+        WorldAabb::screen(&aspect_ratio)
+          .max
+          .to_normalized(&aspect_ratio),
+        Vec2::ONE
+      );
+    }
+  }
+
+  #[test]
+  fn zero_size_window_produces_no_nan() {
+    let aspect_ratio = AspectRatio::new(0.5625);
+    let finite = |vec: WorldVec2| vec.x.0.is_finite() && vec.y.0.is_finite();
+    for win_info in [
+      WinInfo::new(0., 0.),
+      WinInfo::new(0., 720.),
+      WinInfo::new(1280., 0.),
+    ] {
+      for pixels in [Vec2::ZERO, Vec2::new(10., -20.)] {
+        let from_pixels = WorldVec2::new(
+          WorldUnit::from_x(pixels.x, &win_info, &aspect_ratio),
+          WorldUnit::from_y(pixels.y, &win_info, &aspect_ratio),
+        );
+        let from_screen = WorldVec2::from_window_screen_pos(pixels, &win_info, &aspect_ratio);
+        assert!(finite(from_pixels), "{from_pixels:?}");
+        assert!(finite(from_screen), "{from_screen:?}");
+      }
+      let aabb = WorldAabb::from_pixel_size(
+        WorldVec2::ZERO,
+        Vec2::new(10., 10.),
+        &win_info,
+        &aspect_ratio,
+      );
+      assert!(finite(aabb.min) && finite(aabb.max));
+    }
+  }
+
+  #[test]
+  fn screen_extent_matches_window_orientation() {
+    let landscape = AspectRatio::from_window_size(1920., 1080.);
+    assert_eq!(WorldUnit::screen_width(&landscape), unit(50.));
+    assert_eq!(WorldUnit::screen_height(&landscape), unit(28.125));
+
+    let portrait = AspectRatio::from_window_size(1080., 1920.);
+    assert_eq!(WorldUnit::screen_width(&portrait), unit(28.125));
+    assert_eq!(WorldUnit::screen_height(&portrait), unit(50.));
+
+    let win_info = WinInfo::new(1080., 1920.);
+    assert_eq!(WorldUnit::scale(&win_info, &portrait), Vec2::splat(38.4));
+  }
+
+  #[test]
+  fn penetration_depth_measures_distance_to_nearest_edge() {
+    let aabb = WorldAabb::new(vec2(0., 0.), vec2(10., 4.));
+    assert_eq!(aabb.penetration_depth(vec2(12., 2.)), None);
+    assert_eq!(aabb.penetration_depth(vec2(-1., -1.)), None);
+    assert_eq!(aabb.penetration_depth(vec2(5., 1.)), Some(unit(1.)));
+    assert_eq!(aabb.penetration_depth(vec2(9.5, 2.)), Some(unit(0.5)));
+    assert_eq!(aabb.penetration_depth(vec2(0., 2.)), Some(WorldUnit::ZERO));
+  }
+
+  #[test]
+  fn angle_between_and_rotate_around() {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    assert_eq!(WorldVec2::X.angle_between(WorldVec2::Y), FRAC_PI_2);
+    assert_eq!(WorldVec2::Y.angle_between(WorldVec2::X), -FRAC_PI_2);
+
+    let point = vec2(2., 3.);
+    assert_eq!(point.rotate_around(point, PI), point);
+    assert!(
+      vec2(3., 3.)
+        .rotate_around(point, PI)
+        .approx_eq(vec2(1., 3.), unit(1e-6))
+    );
+    assert!(
+      vec2(3., 3.)
+        .rotate_around(point, FRAC_PI_2)
+        .approx_eq(vec2(2., 4.), unit(1e-6))
+    );
+  }
+
+  #[test]
+  fn grid_snapping_rounds_to_spacing() {
+    let point = vec2(1.3, -2.6);
+    assert_eq!(point.snap_to_grid(unit(1.)), vec2(1., -3.));
+    assert_eq!(point.snap_to_grid(unit(0.5)), vec2(1.5, -2.5));
+    assert_eq!(point.floor_to_grid(unit(2.)), vec2(0., -4.));
+    assert_eq!(point.ceil_to_grid(unit(2.)), vec2(2., -2.));
+    assert_eq!(point.snap_to_grid(WorldUnit::ZERO), point);
+  }
+
+  #[test]
+  fn normalizing_zero_is_safe_to_order() {
+    assert_eq!(WorldVec2::ZERO.normalized(), WorldVec2::ZERO);
+    assert_eq!(WorldVec2::ZERO.try_normalize(), None);
+    assert_eq!(vec2(3., 4.).normalized(), vec2(0.6, 0.8));
+
+    // `WorldUnit`'s ordering panics on NaN, so this would panic if normalizing produced NaN.
+    let normalized = WorldVec2::ZERO.normalized();
+    let set = std::collections::BTreeSet::from([normalized.x, normalized.y, unit(1.)]);
+    assert_eq!(set.len(), 2);
+  }
+
+  #[test]
+  fn component_mul_and_scalar_scaling() {
+    assert_eq!(vec2(2., -3.).component_mul(vec2(4., 0.5)), vec2(8., -1.5));
+    assert_eq!(unit(2.) * Vec2::new(0.6, 0.8), vec2(1.2, 1.6));
+    assert_eq!((unit(5.) * Vec2::new(0.6, 0.8)).length(), unit(5.));
+  }
+
+  #[test]
+  fn division_by_scalar() {
+    assert_eq!(vec2(3., -4.) / 2., vec2(1.5, -2.));
+    assert_eq!(vec2(3., -4.).checked_div(2.), Some(vec2(1.5, -2.)));
+    assert_eq!(vec2(3., -4.).checked_div(0.), None);
+    let divided = vec2(3., -4.) / 0.;
+    assert!(divided.x.0.is_infinite() && divided.y.0.is_infinite());
+  }
+
+  #[test]
+  fn move_towards_steps_without_overshooting() {
+    let start = vec2(0., 0.);
+    let target = vec2(3., 4.);
+    assert!(
+      start
+        .move_towards(target, unit(1.))
+        .approx_eq(vec2(0.6, 0.8), unit(1e-6))
+    );
+    assert_eq!(start.move_towards(target, unit(5.)), target);
+    assert_eq!(start.move_towards(target, unit(100.)), target);
+    assert_eq!(start.move_towards(target, WorldUnit::ZERO), start);
+    assert_eq!(target.move_towards(target, WorldUnit::ZERO), target);
+  }
+
+  #[test]
+  fn finiteness_checks_and_sanitizing() {
+    for value in [f32::INFINITY, f32::NEG_INFINITY, f32::NAN] {
+      let value = WorldUnit(value);
+      assert!(!value.is_finite());
+      assert_eq!(value.finite_or_zero(), WorldUnit::ZERO);
+    }
+    assert!(WorldUnit(f32::NAN).is_nan());
+    assert!(!unit(f32::INFINITY).is_nan());
+    assert_eq!(unit(-2.).finite_or_zero(), unit(-2.));
+    assert!(!vec2(1., f32::INFINITY).is_finite());
+    assert!(vec2(1., -1.).is_finite());
+  }
+
+  #[test]
+  fn transform_round_trips_through_world_position() {
+    let win_info = WinInfo::new(1280., 720.);
+    let aspect_ratio = AspectRatio::from_window_size(1280., 720.);
+    let transform = Transform::from_xyz(128., -64., 5.);
+    let pos = WorldVec2::from_transform(&transform, &win_info, &aspect_ratio);
+    assert_eq!(pos, vec2(5., -2.5));
+
+    let mut round_trip = Transform::from_xyz(0., 0., 5.);
+    pos.apply_to_transform(&mut round_trip, &win_info, &aspect_ratio);
+    assert_eq!(round_trip, transform);
+  }
+
+  #[test]
+  fn array_form_round_trips() {
+    let point = vec2(1.5, -0.25);
+    assert_eq!(point.to_array(), [1.5, -0.25]);
+    assert_eq!(WorldVec2::from_array(point.to_array()), point);
+    assert_eq!(WorldVec2::from_array([0., 7.]).to_array(), [0., 7.]);
+  }
+
+  #[test]
+  fn from_points_bounds_all_points() {
+    assert_eq!(WorldAabb::from_points([]), None);
+    assert_eq!(
+      WorldAabb::from_points([vec2(1., 2.)]),
+      Some(WorldAabb::new(vec2(1., 2.), vec2(1., 2.)))
+    );
+    assert_eq!(
+      WorldAabb::from_points([vec2(0., 0.), vec2(2., 1.), vec2(4., 2.)]),
+      Some(WorldAabb::new(vec2(0., 0.), vec2(4., 2.)))
+    );
+    assert_eq!(
+      WorldAabb::from_points([vec2(3., -1.), vec2(-2., 5.), vec2(0., 0.), vec2(1., -4.)]),
+      Some(WorldAabb::new(vec2(-2., -4.), vec2(3., 5.)))
+    );
+  }
+
+  #[test]
+  fn with_component_leaves_other_untouched() {
+    let point = vec2(1., 2.);
+    assert_eq!(point.with_x(unit(5.)), vec2(5., 2.));
+    assert_eq!(point.with_y(unit(5.)), vec2(1., 5.));
+    assert_eq!(WorldVec2::splat(unit(3.)), vec2(3., 3.));
+  }
+
+  #[test]
+  fn try_new_rejects_invalid_dimensions() {
+    assert!(WorldRect::try_new(unit(-1.), unit(1.)).is_none());
+    assert!(WorldRect::try_new(unit(1.), unit(-0.5)).is_none());
+    assert!(WorldRect::try_new(WorldUnit(f32::NAN), unit(1.)).is_none());
+    assert!(WorldRect::try_new(unit(1.), unit(f32::INFINITY)).is_none());
+    let zero = WorldRect::try_new(WorldUnit::ZERO, WorldUnit::ZERO).unwrap();
+    assert_eq!(zero.half_size(), WorldVec2::ZERO);
+    let rect = WorldRect::try_new(unit(2.), unit(4.)).unwrap();
+    assert_eq!(rect.half_size(), vec2(1., 2.));
+  }
+
+  #[test]
+  fn screen_centered_and_window_pixel_conventions() {
+    let win_info = WinInfo::new(100., 100.);
+    let aspect_ratio = AspectRatio::new(1.).with_units_per_screen_max(10.);
+    for (world, centered, window) in [
+      (vec2(0., 0.), Vec2::new(0., 0.), Vec2::new(50., 50.)),
+      (vec2(-5., 5.), Vec2::new(-50., 50.), Vec2::new(0., 0.)),
+      (vec2(5., 5.), Vec2::new(50., 50.), Vec2::new(100., 0.)),
+      (vec2(-5., -5.), Vec2::new(-50., -50.), Vec2::new(0., 100.)),
+      (vec2(5., -5.), Vec2::new(50., -50.), Vec2::new(100., 100.)),
+    ] {
+      assert_eq!(world.to_screen_centered(&win_info, &aspect_ratio), centered);
+      assert_eq!(world.to_window_pixels(&win_info, &aspect_ratio), window);
+      assert_eq!(
+        WorldVec2::from_absolute(centered, &win_info, &aspect_ratio),
+        world
+      );
+      assert_eq!(
+        WorldVec2::from_window_screen_pos(window, &win_info, &aspect_ratio),
+        world
+      );
+    }
+  }
+
+  #[test]
+  fn meters_round_trip_at_several_scales() {
+    for meters_per_unit in [1., 0.25, 16.] {
+      let scale = PhysicsScale { meters_per_unit };
+      let point = vec2(3., -8.);
+      let meters = point.to_meters(&scale);
+      assert_eq!(meters, Vec2::new(3., -8.) * meters_per_unit);
+      assert_eq!(WorldVec2::from_meters(meters, &scale), point);
+      assert_eq!(
+        WorldUnit::from_meters(unit(2.).to_meters(&scale), &scale),
+        unit(2.)
+      );
+    }
+  }
+
+  #[test]
+  fn clamp_to_bounds_respects_half_extent() {
+    let bounds = WorldAabb::new(vec2(-5., -5.), vec2(5., 5.));
+    let half_extent = vec2(1., 2.);
+    assert_eq!(
+      vec2(20., -20.).clamp_to_bounds(&bounds, half_extent),
+      vec2(4., -3.)
+    );
+    assert_eq!(
+      vec2(1., 1.).clamp_to_bounds(&bounds, half_extent),
+      vec2(1., 1.)
+    );
+    assert_eq!(
+      vec2(3., 3.).clamp_to_bounds(&bounds, vec2(10., 1.)),
+      vec2(0., 3.)
+    );
+  }
+
+  #[test]
+  fn sorts_lexicographically_and_by_distance() {
+    let mut points = vec![vec2(1., 2.), vec2(-1., 5.), vec2(1., -3.), vec2(0., 0.)];
+    points.sort_by(WorldVec2::cmp_lexicographic);
+    assert_eq!(
+      points,
+      [vec2(-1., 5.), vec2(0., 0.), vec2(1., -3.), vec2(1., 2.)]
+    );
+
+    let mut points = vec![vec2(3., 0.), vec2(0., 1.), vec2(-1., 0.), vec2(0., -5.)];
+    WorldVec2::sort_by_distance_from(&mut points, WorldVec2::ZERO);
+    assert_eq!(
+      points,
+      [vec2(0., 1.), vec2(-1., 0.), vec2(3., 0.), vec2(0., -5.)]
+    );
+  }
+
+  #[test]
+  fn ray_intersection_returns_entry_distance() {
+    let aabb = WorldAabb::new(vec2(1., 1.), vec2(3., 3.));
+    assert_eq!(
+      aabb.ray_intersection(vec2(0., 2.), vec2(2., 0.)),
+      Some(unit(1.))
+    );
+    assert!(
+      aabb
+        .ray_intersection(vec2(0., 0.), vec2(1., 1.))
+        .unwrap()
+        .approx_eq(unit(std::f32::consts::SQRT_2), unit(1e-6))
+    );
+    assert_eq!(aabb.ray_intersection(vec2(0., 5.), vec2(1., 0.)), None);
+    assert_eq!(aabb.ray_intersection(vec2(5., 2.), vec2(1., 0.)), None);
+    assert_eq!(
+      aabb.ray_intersection(vec2(2., 2.), vec2(0., -1.)),
+      Some(WorldUnit::ZERO)
+    );
+    assert_eq!(aabb.ray_intersection(vec2(0., 2.), WorldVec2::ZERO), None);
+  }
+
+  #[test]
+  fn wrap_to_bounds_wraps_far_outside_points() {
+    let bounds = WorldAabb::new(vec2(-5., 0.), vec2(5., 4.));
+    assert_eq!(vec2(37., -9.).wrap_to_bounds(&bounds), vec2(-3., 3.));
+    assert_eq!(vec2(-23., 14.).wrap_to_bounds(&bounds), vec2(-3., 2.));
+    assert_eq!(vec2(1., 2.).wrap_to_bounds(&bounds), vec2(1., 2.));
+
+    let flat = WorldAabb::new(vec2(-5., 1.), vec2(5., 1.));
+    assert_eq!(vec2(12., 7.).wrap_to_bounds(&flat), vec2(2., 1.));
+  }
+
+  #[test]
+  fn wrapping_helpers_follow_f32() {
+    assert_eq!(unit(-7.).rem_euclid(unit(3.)), unit(2.));
+    assert_eq!(unit(-1.25).fract(), unit(-0.25));
+    assert_eq!(unit(2.).copysign(unit(-0.)), unit(-2.));
+    assert!(unit(1.).rem_euclid(WorldUnit::ZERO).is_nan());
+  }
+
+  #[test]
+  fn oriented_rect_hit_tests_rotated_corners() {
+    use std::f32::consts::{FRAC_PI_4, SQRT_2};
+
+    let rect = WorldOrientedRect {
+      center: vec2(2., 2.),
+      half_size: vec2(1., 1.),
+      rotation: FRAC_PI_4,
+    };
+    assert!(rect.contains(vec2(2., 2.)));
+    assert!(rect.contains(vec2(2., 2. + SQRT_2 - 0.01)));
+    assert!(!rect.contains(vec2(2., 2. + SQRT_2 + 0.01)));
+    assert!(rect.contains(vec2(2. - SQRT_2 + 0.01, 2.)));
+    assert!(!rect.contains(vec2(2. - SQRT_2 - 0.01, 2.)));
+    assert!(!rect.contains(vec2(2.9, 2.9)));
+
+    assert!(
+      rect
+        .closest_point(vec2(2., 10.))
+        .approx_eq(vec2(2., 2. + SQRT_2), unit(1e-5))
+    );
+  }
+
+  #[test]
+  fn smooth_step_hits_endpoints_and_linear_midpoint() {
+    let (a, b) = (vec2(-2., 1.), vec2(4., 5.));
+    assert_eq!(a.smooth_step(b, 0.), a);
+    assert_eq!(a.smooth_step(b, 1.), b);
+    assert_eq!(a.smooth_step(b, 0.5), a.lerp(b, 0.5));
+    assert_eq!(a.smooth_step(b, -1.), a);
+    assert_eq!(a.smooth_step(b, 2.), b);
+    assert!(a.smooth_step(b, 0.25).x < a.lerp(b, 0.25).x);
+    assert_eq!(unit(0.).smooth_step(unit(10.), 0.5), unit(5.));
+  }
+
+  #[test]
+  fn centroid_averages_points() {
+    assert_eq!(WorldVec2::centroid([]), None);
+    assert_eq!(WorldVec2::centroid([vec2(3., -1.)]), Some(vec2(3., -1.)));
+    assert_eq!(
+      WorldVec2::centroid([vec2(0., 0.), vec2(4., 0.), vec2(2., 6.)]),
+      Some(vec2(2., 2.))
+    );
+  }
+
+  #[test]
+  fn validate_in_reports_offending_axis() {
+    let bounds = WorldAabb::new(vec2(-2., -1.), vec2(2., 1.));
+    assert_eq!(vec2(1., 1.).validate_in(&bounds), Ok(vec2(1., 1.)));
+    assert_eq!(
+      vec2(2.5, 0.).validate_in(&bounds),
+      Err(OutOfBoundsError {
+        point: vec2(2.5, 0.),
+        axis: Axis::X,
+        distance: unit(0.5),
+      })
+    );
+    let error = vec2(0., -1.25).validate_in(&bounds).unwrap_err();
+    assert_eq!(
+      error,
+      OutOfBoundsError {
+        point: vec2(0., -1.25),
+        axis: Axis::Y,
+        distance: unit(0.25),
+      }
+    );
+    assert_eq!(
+      error.to_string(),
+      "(0u, -1.25u) is out of bounds by 0.25u along the y axis"
+    );
+  }
 }